@@ -0,0 +1,291 @@
+use crate::{Grid, Rect, Vec2};
+
+/// A set of small non-negative integers stored as one bit each.
+///
+/// The set grows to fit the largest value inserted, so it is suited to
+/// dense domains such as ids from an [`Interner`](crate::Interner).
+#[derive(Clone, Debug, Default)]
+pub struct BitSet {
+    words: Vec<u64>,
+}
+
+impl BitSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates an empty set which can hold values below `capacity`
+    /// without reallocating.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            words: vec![0; capacity.div_ceil(64)],
+        }
+    }
+
+    /// Adds `value` to the set, returning whether it was newly added.
+    #[inline]
+    pub fn insert(&mut self, value: usize) -> bool {
+        let (word, bit) = (value / 64, 1 << (value % 64));
+        if word >= self.words.len() {
+            self.words.resize(word + 1, 0);
+        }
+        let added = self.words[word] & bit == 0;
+        self.words[word] |= bit;
+        added
+    }
+
+    /// Removes `value` from the set, returning whether it was present.
+    #[inline]
+    pub fn remove(&mut self, value: usize) -> bool {
+        let (word, bit) = (value / 64, 1 << (value % 64));
+        match self.words.get_mut(word) {
+            Some(w) if *w & bit != 0 => {
+                *w &= !bit;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    #[inline]
+    pub fn contains(&self, value: usize) -> bool {
+        self.words
+            .get(value / 64)
+            .is_some_and(|w| w & (1 << (value % 64)) != 0)
+    }
+
+    /// Returns the number of values in the set.
+    pub fn len(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.iter().all(|&w| w == 0)
+    }
+
+    pub fn clear(&mut self) {
+        self.words.fill(0);
+    }
+
+    /// Adds every value in `other` to the set.
+    pub fn union_with(&mut self, other: &BitSet) {
+        if other.words.len() > self.words.len() {
+            self.words.resize(other.words.len(), 0);
+        }
+        for (w, o) in self.words.iter_mut().zip(&other.words) {
+            *w |= o;
+        }
+    }
+
+    /// Removes every value not in `other` from the set.
+    pub fn intersect_with(&mut self, other: &BitSet) {
+        for (i, w) in self.words.iter_mut().enumerate() {
+            *w &= other.words.get(i).copied().unwrap_or(0);
+        }
+    }
+
+    /// Returns the words up to the last non-zero one, so that sets with
+    /// the same values compare equal regardless of capacity.
+    fn trimmed(&self) -> &[u64] {
+        let len = self
+            .words
+            .iter()
+            .rposition(|&w| w != 0)
+            .map_or(0, |i| i + 1);
+        &self.words[..len]
+    }
+
+    /// Returns an iterator over the values in ascending order.
+    pub fn iter(&self) -> impl Iterator<Item = usize> + '_ {
+        self.words.iter().enumerate().flat_map(|(i, &word)| {
+            let mut word = word;
+            std::iter::from_fn(move || {
+                if word == 0 {
+                    return None;
+                }
+                let bit = word.trailing_zeros() as usize;
+                word &= word - 1;
+                Some(i * 64 + bit)
+            })
+        })
+    }
+}
+
+impl PartialEq for BitSet {
+    fn eq(&self, other: &Self) -> bool {
+        self.trimmed() == other.trimmed()
+    }
+}
+
+impl Eq for BitSet {}
+
+impl FromIterator<usize> for BitSet {
+    fn from_iter<I: IntoIterator<Item = usize>>(iter: I) -> Self {
+        let mut set = Self::new();
+        set.extend(iter);
+        set
+    }
+}
+
+impl Extend<usize> for BitSet {
+    fn extend<I: IntoIterator<Item = usize>>(&mut self, iter: I) {
+        for value in iter {
+            self.insert(value);
+        }
+    }
+}
+
+/// A set of positions within a fixed-size grid, stored as one bit per
+/// cell, for tracking visited cells without hashing.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BitGrid {
+    cols: usize,
+    rows: usize,
+    bits: BitSet,
+}
+
+impl BitGrid {
+    pub fn new(cols: usize, rows: usize) -> Self {
+        Self {
+            cols,
+            rows,
+            bits: BitSet::with_capacity(cols * rows),
+        }
+    }
+
+    /// Creates an empty set with the same dimensions as `grid`.
+    pub fn like<T>(grid: &Grid<T>) -> Self {
+        Self::new(grid.cols(), grid.rows())
+    }
+
+    #[inline]
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    #[inline]
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    /// Returns the rectangle covering every cell of the grid.
+    #[inline]
+    pub fn bounds(&self) -> Rect {
+        Rect::from_size((0, 0), self.cols, self.rows)
+    }
+
+    #[inline]
+    pub fn in_bounds(&self, &Vec2 { x, y }: &Vec2) -> bool {
+        (x >= 0 && x < self.cols() as i64) && (y >= 0 && y < self.rows() as i64)
+    }
+
+    /// Adds `pos` to the set, returning whether it was newly added.
+    ///
+    /// Panics if `pos` is outside the grid.
+    #[inline]
+    pub fn insert(&mut self, pos: Vec2) -> bool {
+        assert!(self.in_bounds(&pos), "{pos:?} is outside the grid");
+        self.bits.insert(self.calc_index(&pos))
+    }
+
+    /// Removes `pos` from the set, returning whether it was present.
+    #[inline]
+    pub fn remove(&mut self, pos: &Vec2) -> bool {
+        self.in_bounds(pos) && self.bits.remove(self.calc_index(pos))
+    }
+
+    /// Returns whether `pos` is in the set; positions outside the grid
+    /// never are.
+    #[inline]
+    pub fn contains(&self, pos: &Vec2) -> bool {
+        self.in_bounds(pos) && self.bits.contains(self.calc_index(pos))
+    }
+
+    /// Returns the number of positions in the set.
+    pub fn len(&self) -> usize {
+        self.bits.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bits.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.bits.clear();
+    }
+
+    /// Returns an iterator over the positions in the set in row order.
+    pub fn iter(&self) -> impl Iterator<Item = Vec2> + '_ {
+        let cols = self.cols.max(1);
+        self.bits
+            .iter()
+            .map(move |i| Vec2::new((i % cols) as i64, (i / cols) as i64))
+    }
+
+    /// Returns a grid with `true` for every position in the set.
+    pub fn to_grid(&self) -> Grid<bool> {
+        let mut grid = Grid::new(self.cols, self.rows);
+        for pos in self.iter() {
+            grid[pos] = true;
+        }
+        grid
+    }
+
+    #[inline]
+    fn calc_index(&self, &Vec2 { x, y }: &Vec2) -> usize {
+        y as usize * self.cols + x as usize
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bit_set() {
+        let mut set: BitSet = [3, 64, 200].into_iter().collect();
+        assert!(set.insert(5));
+        assert!(!set.insert(64));
+        assert!(set.contains(200) && !set.contains(201) && !set.contains(10_000));
+        assert!(set.remove(3) && !set.remove(3) && !set.remove(10_000));
+        assert_eq!(set.iter().collect::<Vec<_>>(), [5, 64, 200]);
+        assert_eq!(set.len(), 3);
+
+        let other: BitSet = [5, 6, 300].into_iter().collect();
+        let mut union = set.clone();
+        union.union_with(&other);
+        assert_eq!(union.iter().collect::<Vec<_>>(), [5, 6, 64, 200, 300]);
+        set.intersect_with(&other);
+        assert_eq!(set.iter().collect::<Vec<_>>(), [5]);
+        set.clear();
+        assert!(set.is_empty());
+        assert_eq!(set, BitSet::new());
+
+        let mut five: BitSet = [5, 200].into_iter().collect();
+        five.remove(200);
+        assert_eq!(five, [5].into_iter().collect());
+        assert_eq!(BitSet::with_capacity(128), BitSet::new());
+        assert_ne!(five, BitSet::new());
+    }
+
+    #[test]
+    fn bit_grid() {
+        let mut visited = BitGrid::new(3, 2);
+        assert!(visited.insert(Vec2::new(2, 1)));
+        assert!(!visited.insert(Vec2::new(2, 1)));
+        assert!(visited.insert(Vec2::new(0, 1)));
+        assert!(!visited.contains(&Vec2::new(3, 0)));
+        assert!(!visited.contains(&Vec2::new(-1, 1)));
+        assert_eq!(
+            visited.iter().collect::<Vec<_>>(),
+            [Vec2::new(0, 1), Vec2::new(2, 1)]
+        );
+        assert!(visited.to_grid()[Vec2::new(2, 1)]);
+        assert!(visited.remove(&Vec2::new(0, 1)));
+        assert_eq!(visited.len(), 1);
+
+        let mut other = BitGrid::new(3, 2);
+        other.insert(Vec2::new(2, 1));
+        assert_eq!(visited, other);
+    }
+}
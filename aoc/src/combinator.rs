@@ -0,0 +1,244 @@
+//! Small parser combinators for puzzle input.
+//!
+//! A parser is any `Fn(&str) -> PResult<T>` which returns the parsed
+//! value along with the unconsumed input. Failures remember where in the
+//! input they happened, and [`parse_all`] turns them into a
+//! [`ParseError`] with the line and column.
+//!
+//! A day can declare its parser as a combinator expression in
+//! [`setup!`](crate::setup):
+//!
+//! ```ignore
+//! aoc::setup! {
+//!     day05, parse_input: Parsed = pair(
+//!         lines(pair(number(), tag("|"), number())),
+//!         blank_line(),
+//!         lines(sep_by(number(), tag(","))),
+//!     );
+//!     part1 == 143,
+//!     part2 == 123
+//! }
+//! ```
+
+use crate::parse::locate;
+use crate::{gridify_ascii, Grid, ParseError};
+use std::fmt::Display;
+use std::str::FromStr;
+
+/// A parse failure at a position in the input.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Failure<'a> {
+    /// The remaining input at the point of failure.
+    pub at: &'a str,
+    pub message: String,
+}
+
+impl<'a> Failure<'a> {
+    pub fn new(at: &'a str, message: impl ToString) -> Self {
+        Self {
+            at,
+            message: message.to_string(),
+        }
+    }
+}
+
+/// The result of running a parser: the value and the remaining input.
+pub type PResult<'a, T> = Result<(T, &'a str), Failure<'a>>;
+
+/// A parser producing a `T`.
+pub trait Parser<'a, T>: Fn(&'a str) -> PResult<'a, T> {}
+
+impl<'a, T, F> Parser<'a, T> for F where F: Fn(&'a str) -> PResult<'a, T> {}
+
+/// Runs `parser` over the whole of `input`, which may only be followed
+/// by whitespace.
+pub fn parse_all<'a, T>(parser: impl Parser<'a, T>, input: &'a str) -> Result<T, ParseError> {
+    let result = parser(input).and_then(|(value, rest)| match rest.trim().is_empty() {
+        true => Ok(value),
+        false => Err(Failure::new(rest, "unexpected trailing input")),
+    });
+    result.map_err(|failure| {
+        // At the end of the input there is no line, but `at` itself still
+        // points into `input`.
+        let text = failure.at.lines().next().unwrap_or(failure.at);
+        locate(input, text, failure.message)
+    })
+}
+
+/// Parses an optionally signed integer.
+pub fn number<'a, T>() -> impl Parser<'a, T>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    move |input: &'a str| {
+        let sign = usize::from(input.starts_with(['-', '+']));
+        let digits = input[sign..].bytes().take_while(u8::is_ascii_digit).count();
+        if digits == 0 {
+            return Err(Failure::new(input, "expected a number"));
+        }
+        let (token, rest) = input.split_at(sign + digits);
+        match token.parse() {
+            Ok(value) => Ok((value, rest)),
+            Err(err) => Err(Failure::new(input, err)),
+        }
+    }
+}
+
+/// Matches the literal text `tag`.
+pub fn tag<'a>(tag: &'a str) -> impl Parser<'a, &'a str> {
+    move |input: &'a str| match input.strip_prefix(tag) {
+        Some(rest) => Ok((&input[..tag.len()], rest)),
+        None => Err(Failure::new(input, format!("expected {tag:?}"))),
+    }
+}
+
+/// Matches a line ending followed by one or more empty lines, such as
+/// the separator between sections. Accepts `\n` and `\r\n` endings.
+pub fn blank_line<'a>() -> impl Parser<'a, ()> {
+    move |input: &'a str| {
+        let mut rest = line_ending(input)
+            .and_then(line_ending)
+            .ok_or_else(|| Failure::new(input, "expected a blank line"))?;
+        while let Some(next) = line_ending(rest) {
+            rest = next;
+        }
+        Ok(((), rest))
+    }
+}
+
+/// Parses `a`, then `sep`, then `b`, keeping `a` and `b`.
+pub fn pair<'a, A, S, B>(
+    a: impl Parser<'a, A>,
+    sep: impl Parser<'a, S>,
+    b: impl Parser<'a, B>,
+) -> impl Parser<'a, (A, B)> {
+    move |input: &'a str| {
+        let (a, rest) = a(input)?;
+        let (_, rest) = sep(rest)?;
+        let (b, rest) = b(rest)?;
+        Ok(((a, b), rest))
+    }
+}
+
+/// Parses one or more `item`s separated by `sep`.
+pub fn sep_by<'a, T, S>(
+    item: impl Parser<'a, T>,
+    sep: impl Parser<'a, S>,
+) -> impl Parser<'a, Vec<T>> {
+    move |input: &'a str| {
+        let (first, mut rest) = item(input)?;
+        let mut items = vec![first];
+        while let Ok((_, next)) = sep(rest) {
+            let (value, next) = item(next)?;
+            items.push(value);
+            rest = next;
+        }
+        Ok((items, rest))
+    }
+}
+
+/// Applies `f` to the output of `parser`.
+pub fn map<'a, T, U>(parser: impl Parser<'a, T>, f: impl Fn(T) -> U) -> impl Parser<'a, U> {
+    move |input: &'a str| parser(input).map(|(value, rest)| (f(value), rest))
+}
+
+/// Parses `item` on each line up to the next blank line or the end of
+/// the input. The line ending after the last line is not consumed.
+pub fn lines<'a, T>(item: impl Parser<'a, T>) -> impl Parser<'a, Vec<T>> {
+    move |input: &'a str| {
+        let (first, mut rest) = item(input)?;
+        let mut items = vec![first];
+        while let Some(next) = next_line(rest) {
+            let (value, next) = item(next)?;
+            items.push(value);
+            rest = next;
+        }
+        Ok((items, rest))
+    }
+}
+
+/// Parses a rectangular grid of ASCII characters up to the next blank
+/// line or the end of the input.
+pub fn grid<'a>() -> impl Parser<'a, Grid<u8>> {
+    move |input: &'a str| {
+        let mut rows: Vec<&str> = vec![];
+        let mut rest = input;
+        loop {
+            let (row, after) = rest.split_at(rest.find(['\r', '\n']).unwrap_or(rest.len()));
+            if row.is_empty() {
+                return Err(Failure::new(rest, "expected a grid row"));
+            }
+            if let Some(first) = rows.first().filter(|first| first.len() != row.len()) {
+                let message = format!("expected a row of length {}", first.len());
+                return Err(Failure::new(row, message));
+            }
+            rows.push(row);
+            match next_line(after) {
+                Some(next) => rest = next,
+                None => return Ok((gridify_ascii(rows.into_iter()), after)),
+            }
+        }
+    }
+}
+
+fn line_ending(input: &str) -> Option<&str> {
+    input
+        .strip_prefix("\r\n")
+        .or_else(|| input.strip_prefix('\n'))
+}
+
+/// Returns the input after the line ending at the start of `input`, if
+/// it is followed by a non-blank line.
+fn next_line(input: &str) -> Option<&str> {
+    line_ending(input).filter(|next| !next.is_empty() && line_ending(next).is_none())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn combinators() {
+        let input = "47|53\r\n97|13\r\n\r\n\n75,47,61\n97,61\n";
+        let parser = pair(
+            lines(pair(number::<u8>(), tag("|"), number())),
+            blank_line(),
+            lines(sep_by(number::<u8>(), tag(","))),
+        );
+        let (rules, updates) = parse_all(&parser, input).unwrap();
+        assert_eq!(rules, [(47, 53), (97, 13)]);
+        assert_eq!(updates, [vec![75, 47, 61], vec![97, 61]]);
+
+        let err = parse_all(&parser, "47|53\n97-13\n\n1").unwrap_err();
+        assert_eq!(
+            (err.line, err.column, err.message.as_str()),
+            (2, 3, "expected \"|\"")
+        );
+        let err = parse_all(&parser, "47|53\n\n1,999").unwrap_err();
+        assert_eq!((err.line, err.column, err.text.as_str()), (3, 3, "999"));
+        let err = parse_all(&parser, "47|53\n").unwrap_err();
+        assert_eq!(
+            (err.line, err.message.as_str()),
+            (1, "expected a blank line")
+        );
+
+        let err = parse_all(
+            lines(pair(number::<u32>(), tag(","), number::<u32>())),
+            "1,2\n3,",
+        );
+        assert_eq!(
+            err.map_err(|err| (err.line, err.column, err.message)),
+            Err((2, 3, "expected a number".to_string()))
+        );
+
+        let (parsed, rest) = grid()("#.\n.#\n\nrest").unwrap();
+        assert_eq!((parsed.cols(), parsed.rows(), rest), (2, 2, "\n\nrest"));
+        let err = parse_all(grid(), "#.\n.#.").unwrap_err();
+        assert_eq!(
+            (err.line, err.message.as_str()),
+            (2, "expected a row of length 2")
+        );
+        assert_eq!(parse_all(map(number::<i8>(), |n| -n), "-5"), Ok(5));
+    }
+}
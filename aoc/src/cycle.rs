@@ -0,0 +1,117 @@
+//! Cycle detection for iterated state machines.
+//!
+//! Each function takes an initial state and a `step` function, and
+//! describes the sequence `initial, step(initial), ...` by `(mu,
+//! lambda)`: the index of the first state in the cycle and the length
+//! of the cycle.
+
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// Finds the cycle using Brent's algorithm, which only needs to compare
+/// states and keeps at most two of them.
+pub fn find<S: Clone + Eq>(initial: S, mut step: impl FnMut(&S) -> S) -> (usize, usize) {
+    // Find the cycle length by moving the tortoise to the hare every
+    // power of two steps.
+    let mut power = 1;
+    let mut lambda = 1;
+    let mut tortoise = initial.clone();
+    let mut hare = step(&initial);
+    while tortoise != hare {
+        if power == lambda {
+            tortoise = hare.clone();
+            power *= 2;
+            lambda = 0;
+        }
+        hare = step(&hare);
+        lambda += 1;
+    }
+
+    // Find the start of the cycle with the hare `lambda` steps ahead.
+    let mut tortoise = initial.clone();
+    let mut hare = initial;
+    for _ in 0..lambda {
+        hare = step(&hare);
+    }
+    let mut mu = 0;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        mu += 1;
+    }
+    (mu, lambda)
+}
+
+/// Finds the cycle by remembering every state, which calls `step` only
+/// `mu + lambda` times.
+pub fn find_hashed<S: Clone + Eq + Hash>(initial: S, step: impl FnMut(&S) -> S) -> (usize, usize) {
+    let (mu, lambda, _) = history(initial, step, None);
+    (mu, lambda)
+}
+
+/// Returns the state after `n` steps, using the cycle to skip ahead
+/// once a state repeats.
+pub fn nth_state<S: Clone + Eq + Hash>(initial: S, step: impl FnMut(&S) -> S, n: usize) -> S {
+    let (mu, lambda, mut states) = history(initial, step, Some(n));
+    let index = match n < states.len() {
+        true => n,
+        false => equivalent_step((mu, lambda), n),
+    };
+    states.swap_remove(index)
+}
+
+/// Maps step `n` to the equivalent step within the first pass of the
+/// cycle described by `(mu, lambda)`.
+#[inline]
+pub fn equivalent_step((mu, lambda): (usize, usize), n: usize) -> usize {
+    match n < mu {
+        true => n,
+        false => mu + (n - mu) % lambda,
+    }
+}
+
+/// Records states until one repeats or, if specified, `limit` steps have
+/// been taken.
+fn history<S: Clone + Eq + Hash>(
+    initial: S,
+    mut step: impl FnMut(&S) -> S,
+    limit: Option<usize>,
+) -> (usize, usize, Vec<S>) {
+    let mut seen = HashMap::new();
+    let mut states = vec![];
+    let mut state = initial;
+    loop {
+        if let Some(&mu) = seen.get(&state) {
+            return (mu, states.len() - mu, states);
+        }
+        seen.insert(state.clone(), states.len());
+        let next = step(&state);
+        states.push(state);
+        if limit.is_some_and(|limit| states.len() > limit) {
+            return (0, 0, states);
+        }
+        state = next;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn find_cycles() {
+        // 3, 10, 5, 16, 8, 4, 2, 1, 4, ...
+        let collatz = |&n: &u64| if n % 2 == 0 { n / 2 } else { 3 * n + 1 };
+        assert_eq!(find(3, collatz), (5, 3));
+        assert_eq!(find_hashed(3, collatz), (5, 3));
+        assert_eq!(find(1, collatz), (0, 3));
+
+        assert_eq!(nth_state(3, collatz, 4), 8);
+        assert_eq!(nth_state(3, collatz, 1_000_000_000), 1);
+        assert_eq!(equivalent_step((5, 3), 1_000_000_000), 7);
+        assert_eq!(equivalent_step((5, 3), 2), 2);
+
+        let step = |&n: &u32| (n * n + 1) % 255;
+        assert_eq!(find(0, step), find_hashed(0, step));
+    }
+}
@@ -0,0 +1,293 @@
+//! Graphs with interned node labels.
+//!
+//! Nodes are identified by the dense `u32` ids of an [`Interner`], so
+//! per-node data can be kept in a [`Table`].
+
+use crate::{Interner, Table};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::hash::Hash;
+
+/// A graph whose nodes are labelled by `N`, such as `&str` names.
+#[derive(Clone, Debug)]
+pub struct Graph<N> {
+    nodes: Interner<N>,
+    adjacency: Table<HashSet<u32>>,
+}
+
+impl<N> Default for Graph<N> {
+    fn default() -> Self {
+        Self {
+            nodes: Interner::default(),
+            adjacency: Table::new(),
+        }
+    }
+}
+
+impl<N: Hash + Eq + Clone> Graph<N> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Builds an undirected graph from pairs of connected labels, such as
+    /// the halves of `a-b` lines.
+    pub fn undirected(pairs: impl IntoIterator<Item = (N, N)>) -> Self {
+        let mut graph = Self::new();
+        for (a, b) in pairs {
+            graph.add_edge(a, b);
+        }
+        graph
+    }
+
+    /// Builds a directed graph with an edge from the first label of each
+    /// pair to the second.
+    pub fn directed(pairs: impl IntoIterator<Item = (N, N)>) -> Self {
+        let mut graph = Self::new();
+        for (a, b) in pairs {
+            graph.add_directed_edge(a, b);
+        }
+        graph
+    }
+
+    /// Returns the id of the node with the specified label, adding it if
+    /// it does not exist.
+    pub fn add_node(&mut self, label: N) -> u32 {
+        let id = self.nodes.intern(label);
+        if id as usize == self.adjacency.len() {
+            self.adjacency.push(HashSet::new());
+        }
+        id
+    }
+
+    pub fn add_edge(&mut self, a: N, b: N) {
+        let (a, b) = (self.add_node(a), self.add_node(b));
+        self.adjacency[a].insert(b);
+        self.adjacency[b].insert(a);
+    }
+
+    pub fn add_directed_edge(&mut self, from: N, to: N) {
+        let (from, to) = (self.add_node(from), self.add_node(to));
+        self.adjacency[from].insert(to);
+    }
+
+    /// Returns the id of the node with the specified label.
+    pub fn id(&self, label: &N) -> Option<u32> {
+        self.nodes.id(label)
+    }
+}
+
+impl<N> Graph<N> {
+    #[inline]
+    pub fn label(&self, id: u32) -> &N {
+        self.nodes.label(id)
+    }
+
+    /// Returns the node labels and their ids.
+    #[inline]
+    pub fn nodes(&self) -> &Interner<N> {
+        &self.nodes
+    }
+
+    /// Returns the number of nodes.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    /// Returns the nodes reachable from `id` by a single edge.
+    #[inline]
+    pub fn neighbours(&self, id: u32) -> &HashSet<u32> {
+        &self.adjacency[id]
+    }
+
+    #[inline]
+    pub fn is_adjacent(&self, a: u32, b: u32) -> bool {
+        self.adjacency[a].contains(&b)
+    }
+
+    /// Iterates over every set of three mutually adjacent nodes, with the
+    /// ids in each triangle in ascending order.
+    pub fn triangles(&self) -> impl Iterator<Item = [u32; 3]> + '_ {
+        (0..self.len() as u32).flat_map(move |a| {
+            self.adjacency[a]
+                .iter()
+                .filter(move |&&b| b > a)
+                .flat_map(move |&b| {
+                    self.adjacency[b]
+                        .iter()
+                        .filter(move |&&c| c > b && self.is_adjacent(a, c))
+                        .map(move |&c| [a, b, c])
+                })
+        })
+    }
+
+    /// Returns a largest set of mutually adjacent nodes, found with the
+    /// Bron–Kerbosch algorithm.
+    pub fn max_clique(&self) -> Vec<u32> {
+        fn expand(
+            adjacency: &Table<HashSet<u32>>,
+            clique: &mut Vec<u32>,
+            mut candidates: HashSet<u32>,
+            mut excluded: HashSet<u32>,
+            best: &mut Vec<u32>,
+        ) {
+            if candidates.is_empty() && excluded.is_empty() {
+                if clique.len() > best.len() {
+                    best.clone_from(clique);
+                }
+                return;
+            }
+            if clique.len() + candidates.len() <= best.len() {
+                return;
+            }
+
+            // Only try nodes which are not neighbours of the pivot; any
+            // clique containing a neighbour can be extended by the pivot.
+            let pivot = candidates
+                .union(&excluded)
+                .max_by_key(|&&u| adjacency[u].intersection(&candidates).count())
+                .copied()
+                .unwrap();
+            let remaining: Vec<_> = candidates.difference(&adjacency[pivot]).copied().collect();
+            for v in remaining {
+                let neighbours = &adjacency[v];
+                clique.push(v);
+                expand(
+                    adjacency,
+                    clique,
+                    candidates.intersection(neighbours).copied().collect(),
+                    excluded.intersection(neighbours).copied().collect(),
+                    best,
+                );
+                clique.pop();
+                candidates.remove(&v);
+                excluded.insert(v);
+            }
+        }
+
+        let mut best = vec![];
+        let candidates = (0..self.len() as u32).collect();
+        expand(
+            &self.adjacency,
+            &mut vec![],
+            candidates,
+            HashSet::new(),
+            &mut best,
+        );
+        best.sort();
+        best
+    }
+
+    /// Orders the nodes so that every edge points forwards, using Kahn's
+    /// algorithm.
+    ///
+    /// If the graph contains a cycle, returns the nodes which could not
+    /// be ordered: those on a cycle or reachable from one.
+    pub fn topological_sort(&self) -> Result<Vec<u32>, Vec<u32>> {
+        let mut in_degree = self.nodes.table(0);
+        for &to in self.adjacency.values().iter().flatten() {
+            in_degree[to] += 1;
+        }
+        let mut queue: VecDeque<_> = in_degree
+            .iter()
+            .filter(|&(_, &d)| d == 0)
+            .map(|(n, _)| n)
+            .collect();
+        let mut order = Vec::with_capacity(self.len());
+        while let Some(node) = queue.pop_front() {
+            order.push(node);
+            for &to in &self.adjacency[node] {
+                in_degree[to] -= 1;
+                if in_degree[to] == 0 {
+                    queue.push_back(to);
+                }
+            }
+        }
+        match order.len() == self.len() {
+            true => Ok(order),
+            false => Err(in_degree
+                .iter()
+                .filter(|&(_, &d)| d > 0)
+                .map(|(n, _)| n)
+                .collect()),
+        }
+    }
+
+    /// Returns the connected components, ignoring edge direction, each
+    /// in ascending order of id.
+    pub fn components(&self) -> Vec<Vec<u32>> {
+        fn find(parent: &mut Table<u32>, mut node: u32) -> u32 {
+            while parent[node] != node {
+                parent[node] = parent[parent[node]];
+                node = parent[node];
+            }
+            node
+        }
+
+        let mut parent = self.nodes.table_with(|(id, _)| id);
+        for (a, neighbours) in self.adjacency.iter() {
+            for &b in neighbours {
+                let (ra, rb) = (find(&mut parent, a), find(&mut parent, b));
+                parent[ra.max(rb)] = ra.min(rb);
+            }
+        }
+
+        let mut components: Vec<Vec<u32>> = vec![];
+        let mut index = HashMap::new();
+        for node in 0..self.len() as u32 {
+            let root = find(&mut parent, node);
+            let i = *index.entry(root).or_insert_with(|| {
+                components.push(vec![]);
+                components.len() - 1
+            });
+            components[i].push(node);
+        }
+        components
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn undirected_graph() {
+        let edges = "a-b\nb-c\nc-a\nc-d\nd-b\nd-a\ne-f\ng-c";
+        let graph = Graph::undirected(edges.lines().filter_map(|s| s.split_once('-')));
+        assert_eq!(graph.len(), 7);
+        assert_eq!(graph.id(&"c"), Some(2));
+        assert_eq!(graph.label(4), &"e");
+        assert_eq!(graph.triangles().count(), 4);
+        assert!(graph.triangles().all(|[a, b, c]| a < b && b < c));
+
+        let clique: Vec<_> = graph
+            .max_clique()
+            .into_iter()
+            .map(|n| *graph.label(n))
+            .collect();
+        assert_eq!(clique, ["a", "b", "c", "d"]);
+        assert_eq!(graph.components(), [vec![0, 1, 2, 3, 6], vec![4, 5]]);
+    }
+
+    #[test]
+    fn directed_graph() {
+        let mut graph = Graph::directed([(3, 1), (1, 2), (3, 2)]);
+        graph.add_node(4);
+        let order: Vec<_> = graph
+            .topological_sort()
+            .unwrap()
+            .into_iter()
+            .map(|n| *graph.label(n))
+            .collect();
+        assert_eq!(order, [3, 4, 1, 2]);
+        assert_eq!(graph.components().len(), 2);
+
+        graph.add_directed_edge(2, 3);
+        graph.add_directed_edge(2, 5);
+        assert_eq!(graph.topological_sort(), Err(vec![0, 1, 2, 4]));
+    }
+}
@@ -1,5 +1,6 @@
-use crate::Vec2;
+use crate::{Rect, Vec2};
 use core::fmt;
+use rayon::prelude::*;
 
 /// Cardinal directions.
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -49,6 +50,60 @@ impl Cardinal {
         }
     }
 
+    /// Returns the arrow character for the direction (`^`, `>`, `v`
+    /// or `<`).
+    pub fn to_arrow(self) -> char {
+        match self {
+            Self::North => '^',
+            Self::East => '>',
+            Self::South => 'v',
+            Self::West => '<',
+        }
+    }
+
+    /// Returns the upper-case initial of the direction (`N`, `E`, `S`
+    /// or `W`).
+    pub fn to_letter(self) -> char {
+        match self {
+            Self::North => 'N',
+            Self::East => 'E',
+            Self::South => 'S',
+            Self::West => 'W',
+        }
+    }
+
+    /// Returns the direction after making the specified turn.
+    pub fn turn(self, turn: Turn) -> Self {
+        Self::from_index(self.index() + turn as usize)
+    }
+
+    /// Returns the turn needed to face `other` from this direction.
+    pub fn turn_to(self, other: Cardinal) -> Turn {
+        match (other.index() + 4 - self.index()) % 4 {
+            0 => Turn::Straight,
+            1 => Turn::Right,
+            2 => Turn::Around,
+            _ => Turn::Left,
+        }
+    }
+
+    /// Returns the position of the direction in clockwise order
+    /// starting from [`Cardinal::North`].
+    #[inline]
+    pub fn index(self) -> usize {
+        self as usize
+    }
+
+    /// Inverse of [`Cardinal::index`], wrapping modulo 4.
+    pub fn from_index(index: usize) -> Self {
+        match index % 4 {
+            0 => Self::North,
+            1 => Self::East,
+            2 => Self::South,
+            _ => Self::West,
+        }
+    }
+
     /// Iterates over the cardinal directions once.
     pub fn iter() -> impl Iterator<Item = Self> {
         use Cardinal::*;
@@ -56,6 +111,49 @@ impl Cardinal {
     }
 }
 
+impl TryFrom<u8> for Cardinal {
+    type Error = String;
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        Self::from_ascii(value)
+            .ok_or_else(|| format!("{:?} is not a cardinal direction", value as char))
+    }
+}
+
+impl TryFrom<char> for Cardinal {
+    type Error = String;
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        u8::try_from(value)
+            .ok()
+            .and_then(Self::from_ascii)
+            .ok_or_else(|| format!("{value:?} is not a cardinal direction"))
+    }
+}
+
+/// Relative turns, in clockwise quarter turns.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Turn {
+    Straight,
+    Right,
+    Around,
+    Left,
+}
+
+impl<T> std::ops::Index<Cardinal> for [T; 4] {
+    type Output = T;
+
+    #[inline]
+    fn index(&self, direction: Cardinal) -> &Self::Output {
+        &self[direction.index()]
+    }
+}
+
+impl<T> std::ops::IndexMut<Cardinal> for [T; 4] {
+    #[inline]
+    fn index_mut(&mut self, direction: Cardinal) -> &mut Self::Output {
+        &mut self[direction.index()]
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct Grid<T> {
     rows: usize,
@@ -81,6 +179,72 @@ impl<T: Clone> Grid<T> {
             data: vec![value; cols * rows],
         }
     }
+
+    /// Returns a copy of the grid rotated 90 degrees clockwise.
+    pub fn rotate_cw(&self) -> Self {
+        let (cols, rows) = (self.rows, self.cols);
+        let data = (0..rows)
+            .flat_map(|y| (0..cols).map(move |x| (y, self.rows - 1 - x)))
+            .map(|(x, y)| self.data[y * self.cols + x].clone())
+            .collect();
+        Self { rows, cols, data }
+    }
+
+    /// Returns a copy of the grid rotated 90 degrees counter-clockwise.
+    pub fn rotate_ccw(&self) -> Self {
+        let (cols, rows) = (self.rows, self.cols);
+        let data = (0..rows)
+            .flat_map(|y| (0..cols).map(move |x| (self.cols - 1 - y, x)))
+            .map(|(x, y)| self.data[y * self.cols + x].clone())
+            .collect();
+        Self { rows, cols, data }
+    }
+
+    /// Returns a copy of the grid mirrored left-to-right.
+    pub fn flip_h(&self) -> Self {
+        let data = self
+            .data
+            .chunks(self.cols.max(1))
+            .flat_map(|row| row.iter().rev().cloned())
+            .collect();
+        Self { data, ..*self }
+    }
+
+    /// Returns a copy of the grid mirrored top-to-bottom.
+    pub fn flip_v(&self) -> Self {
+        let data = self
+            .data
+            .chunks(self.cols.max(1))
+            .rev()
+            .flat_map(|row| row.iter().cloned())
+            .collect();
+        Self { data, ..*self }
+    }
+
+    /// Returns a copy of the grid with rows and columns swapped.
+    pub fn transpose(&self) -> Self {
+        let (cols, rows) = (self.rows, self.cols);
+        let data = (0..rows)
+            .flat_map(|y| (0..cols).map(move |x| (y, x)))
+            .map(|(x, y)| self.data[y * self.cols + x].clone())
+            .collect();
+        Self { rows, cols, data }
+    }
+
+    /// Returns a copy of the grid with every cell repeated `factor`
+    /// times horizontally.
+    pub fn scale_horizontal(&self, factor: usize) -> Self {
+        let data = self
+            .data
+            .iter()
+            .flat_map(|value| std::iter::repeat_n(value, factor).cloned())
+            .collect();
+        Self {
+            rows: self.rows,
+            cols: self.cols * factor,
+            data,
+        }
+    }
 }
 
 impl<T> Grid<T> {
@@ -164,15 +328,69 @@ impl<T> Grid<T> {
         })
     }
 
-    pub fn cursor(&self, pos: Vec2) -> Cursor<T> {
+    /// Returns a new grid with `f` applied to every cell.
+    pub fn map<U, F>(&self, f: F) -> Grid<U>
+    where
+        F: FnMut(&T) -> U,
+    {
+        Grid {
+            rows: self.rows,
+            cols: self.cols,
+            data: self.data.iter().map(f).collect(),
+        }
+    }
+
+    /// Returns a new grid where every cell is replaced by the `N` cells
+    /// returned by `f`, laid out horizontally.
+    pub fn scale_horizontal_with<U, F, const N: usize>(&self, f: F) -> Grid<U>
+    where
+        F: FnMut(&T) -> [U; N],
+    {
+        Grid {
+            rows: self.rows,
+            cols: self.cols * N,
+            data: self.data.iter().flat_map(f).collect(),
+        }
+    }
+
+    /// Returns a read-only view of the region of the grid covered by
+    /// `rect`.
+    ///
+    /// Panics if the rectangle does not fit inside the grid.
+    pub fn subgrid(&self, rect: Rect) -> SubGrid<'_, T> {
+        assert!(
+            self.bounds().contains_rect(&rect),
+            "subgrid {rect:?} exceeds grid bounds {:?}",
+            self.bounds()
+        );
+        SubGrid {
+            grid: self,
+            origin: rect.min,
+            cols: rect.width() as usize,
+            rows: rect.height() as usize,
+        }
+    }
+
+    pub fn cursor(&self, pos: Vec2) -> Cursor<'_, T> {
         Cursor { grid: self, pos }
     }
 
+    pub fn cursor_mut(&mut self, pos: Vec2) -> CursorMut<'_, T> {
+        CursorMut { grid: self, pos }
+    }
+
     #[inline]
     fn calc_index(&self, &Vec2 { x, y }: &Vec2) -> usize {
         ((y * self.cols() as i64) + x) as usize
     }
 
+    /// Returns the rectangle covering every cell of the grid.
+    #[inline]
+    pub fn bounds(&self) -> Rect {
+        Rect::from_size((0, 0), self.cols(), self.rows())
+    }
+
+    #[inline]
     pub fn in_bounds(&self, &Vec2 { x, y }: &Vec2) -> bool {
         (x >= 0 && x < self.cols() as i64) && (y >= 0 && y < self.rows() as i64)
     }
@@ -196,6 +414,75 @@ impl<T: Eq> Grid<T> {
     }
 }
 
+impl<T: Sync> Grid<T> {
+    /// Returns a parallel iterator of all the valid coordinates in the
+    /// grid, in the same order as [`Grid::iter_pos`].
+    pub fn par_iter_pos(&self) -> impl IndexedParallelIterator<Item = Vec2> {
+        let cols = self.cols().max(1);
+        (0..self.rows() * self.cols())
+            .into_par_iter()
+            .map(move |index| Vec2::from((index % cols, index / cols)))
+    }
+
+    /// Returns a parallel iterator over the rows of the grid.
+    pub fn par_rows(&self) -> impl IndexedParallelIterator<Item = &[T]> {
+        self.data.par_chunks(self.cols().max(1))
+    }
+
+    /// Parallel version of [`Grid::position_all`].
+    pub fn par_position_all<P>(&self, predicate: P) -> Vec<Vec2>
+    where
+        P: Fn(&T) -> bool + Sync,
+    {
+        self.par_iter_pos()
+            .filter(|&pos| predicate(&self[pos]))
+            .collect()
+    }
+}
+
+impl<T: Send> Grid<T> {
+    /// Returns a parallel iterator over mutable rows of the grid.
+    pub fn par_rows_mut(&mut self) -> impl IndexedParallelIterator<Item = &mut [T]> {
+        let cols = self.cols().max(1);
+        self.data.par_chunks_mut(cols)
+    }
+}
+
+impl<T: PartialEq> Grid<T> {
+    /// Shifts the run of cells starting at `pos` one step in the
+    /// specified direction, into the first `empty` cell along the way.
+    ///
+    /// Every cell in the run must satisfy `movable`. On success the cell
+    /// at `pos` becomes `empty` and true is returned, otherwise the grid
+    /// is left untouched and false is returned. An `empty` cell at `pos`
+    /// has nothing to push, so false is returned for it too.
+    pub fn push_line<P>(&mut self, pos: Vec2, direction: Cardinal, empty: &T, movable: P) -> bool
+    where
+        P: Fn(&T) -> bool,
+    {
+        if self.get(&pos) == Some(empty) {
+            return false;
+        }
+        let step = Vec2::from(direction);
+        let mut end = pos;
+        loop {
+            match self.get(&end) {
+                Some(value) if value == empty => break,
+                Some(value) if movable(value) => end += step,
+                _ => return false,
+            }
+        }
+
+        while end != pos {
+            let prev = end - step;
+            let (a, b) = (self.calc_index(&prev), self.calc_index(&end));
+            self.data.swap(a, b);
+            end = prev;
+        }
+        true
+    }
+}
+
 impl<T> std::ops::Index<Vec2> for Grid<T> {
     type Output = T;
 
@@ -216,11 +503,13 @@ impl<T> std::ops::Index<(usize, usize)> for Grid<T> {
 
     #[inline]
     fn index(&self, (x, y): (usize, usize)) -> &Self::Output {
-        let pos = Vec2 {
-            x: x.try_into().unwrap(),
-            y: y.try_into().unwrap(),
-        };
-        &self[pos]
+        assert!(
+            x < self.cols() && y < self.rows(),
+            "({x}, {y}) not in grid bounded by (0, 0) -> ({}, {})",
+            self.cols(),
+            self.rows()
+        );
+        &self.data[y * self.cols() + x]
     }
 }
 
@@ -239,11 +528,14 @@ impl<T> std::ops::IndexMut<Vec2> for Grid<T> {
 
 impl<T> std::ops::IndexMut<(usize, usize)> for Grid<T> {
     fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut Self::Output {
-        let pos = Vec2 {
-            x: x.try_into().unwrap(),
-            y: y.try_into().unwrap(),
-        };
-        &mut self[pos]
+        assert!(
+            x < self.cols() && y < self.rows(),
+            "({x}, {y}) not in grid bounded by (0, 0) -> ({}, {})",
+            self.cols(),
+            self.rows()
+        );
+        let cols = self.cols();
+        &mut self.data[y * cols + x]
     }
 }
 
@@ -303,6 +595,120 @@ impl fmt::Display for Grid<bool> {
     }
 }
 
+/// A read-only rectangular view into a [`Grid`].
+///
+/// Positions are relative to the top-left corner of the view.
+#[derive(Debug)]
+pub struct SubGrid<'g, T> {
+    grid: &'g Grid<T>,
+    origin: Vec2,
+    cols: usize,
+    rows: usize,
+}
+
+impl<T> Clone for SubGrid<'_, T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for SubGrid<'_, T> {}
+
+impl<'g, T> SubGrid<'g, T> {
+    #[inline]
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    #[inline]
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    /// Returns the position of the top-left corner of the view in the
+    /// underlying grid.
+    #[inline]
+    pub fn origin(&self) -> Vec2 {
+        self.origin
+    }
+
+    /// Returns the rectangle covered by the view in the underlying grid.
+    #[inline]
+    pub fn bounds(&self) -> Rect {
+        Rect::from_size(self.origin, self.cols, self.rows)
+    }
+
+    #[inline]
+    pub fn get(&self, pos: &Vec2) -> Option<&'g T> {
+        match self.in_bounds(pos) {
+            true => self.grid.get(&(self.origin + *pos)),
+            false => None,
+        }
+    }
+
+    pub fn in_bounds(&self, &Vec2 { x, y }: &Vec2) -> bool {
+        (x >= 0 && x < self.cols() as i64) && (y >= 0 && y < self.rows() as i64)
+    }
+
+    /// Returns a column-first iterator of all the valid coordinates
+    /// in the view.
+    pub fn iter_pos(&self) -> impl Iterator<Item = Vec2> {
+        let cols = self.cols() as i64;
+        (0..self.rows() as i64).flat_map(move |y| (0..cols).map(move |x| Vec2::new(x, y)))
+    }
+
+    /// Returns an iterator over the cells in a single row of the
+    /// view in column order.
+    pub fn iter_row(&self, row: usize) -> impl Iterator<Item = &'g T> {
+        assert!(row < self.rows(), "row {row} not in subgrid");
+        let (grid, origin) = (self.grid, self.origin);
+        (0..self.cols()).map(move |column| &grid[origin + Vec2::from((column, row))])
+    }
+
+    /// Returns an iterator over the cells in a single column of the
+    /// view in row order.
+    pub fn iter_column(&self, column: usize) -> impl Iterator<Item = &'g T> {
+        assert!(column < self.cols(), "column {column} not in subgrid");
+        let (grid, origin) = (self.grid, self.origin);
+        (0..self.rows()).map(move |row| &grid[origin + Vec2::from((column, row))])
+    }
+}
+
+impl<T: Clone> SubGrid<'_, T> {
+    /// Copies the view into a new, owned [`Grid`].
+    pub fn to_grid(&self) -> Grid<T> {
+        Grid {
+            rows: self.rows,
+            cols: self.cols,
+            data: self.iter_pos().map(|pos| self[pos].clone()).collect(),
+        }
+    }
+}
+
+impl<T> std::ops::Index<Vec2> for SubGrid<'_, T> {
+    type Output = T;
+
+    #[inline]
+    fn index(&self, pos: Vec2) -> &Self::Output {
+        assert!(
+            self.in_bounds(&pos),
+            "{pos} not in subgrid bounded by (0, 0) -> ({}, {})",
+            self.cols(),
+            self.rows()
+        );
+        &self.grid[self.origin + pos]
+    }
+}
+
+impl<T> std::ops::Index<(usize, usize)> for SubGrid<'_, T> {
+    type Output = T;
+
+    #[inline]
+    fn index(&self, (x, y): (usize, usize)) -> &Self::Output {
+        &self[Vec2::from((x, y))]
+    }
+}
+
 /// A two-dimensional cursor associated with a [`Grid`].
 #[derive(Clone, Debug)]
 pub struct Cursor<'g, T> {
@@ -409,6 +815,100 @@ impl<'g, T> Cursor<'g, T> {
     }
 }
 
+/// A two-dimensional cursor associated with a [`Grid`] that can modify
+/// the cells it visits.
+#[derive(Debug)]
+pub struct CursorMut<'g, T> {
+    grid: &'g mut Grid<T>,
+    pos: Vec2,
+}
+
+impl<'g, T> CursorMut<'g, T> {
+    /// Creates a new `CursorMut` for the specified grid, starting at `(0, 0)`.
+    pub fn new(grid: &'g mut Grid<T>) -> Self {
+        Self {
+            grid,
+            pos: Vec2::new(0, 0),
+        }
+    }
+
+    /// Returns the current position of the `CursorMut`.
+    #[inline]
+    pub fn pos(&self) -> Vec2 {
+        self.pos
+    }
+
+    /// Returns the value of the grid cell for the current cursor
+    /// position.
+    #[inline]
+    pub fn value(&self) -> &T {
+        &self.grid[self.pos]
+    }
+
+    /// Returns a mutable reference to the grid cell for the current
+    /// cursor position.
+    #[inline]
+    pub fn value_mut(&mut self) -> &mut T {
+        &mut self.grid[self.pos]
+    }
+
+    /// Replaces the value of the grid cell for the current cursor
+    /// position, returning the old value.
+    #[inline]
+    pub fn set(&mut self, value: T) -> T {
+        self.grid.set(self.pos, value)
+    }
+
+    /// Moves the cursor in the specified direction.
+    ///
+    /// Returns false if the cursor could not be moved (eg. if moving
+    /// the cursor places it outside the bounds of the grid).
+    pub fn step(&mut self, direction: Cardinal) -> bool {
+        let pos = self.pos + Vec2::from(direction);
+        if self.grid.in_bounds(&pos) {
+            self.pos = pos;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Peeks at the cell in the specified direction.
+    pub fn peek(&self, direction: Cardinal) -> Option<&T> {
+        self.grid.get(&(self.pos + Vec2::from(direction)))
+    }
+
+    /// Returns a mutable reference to the cell in the specified
+    /// direction.
+    pub fn peek_mut(&mut self, direction: Cardinal) -> Option<&mut T> {
+        let pos = self.pos + Vec2::from(direction);
+        match self.grid.in_bounds(&pos) {
+            true => Some(&mut self.grid[pos]),
+            false => None,
+        }
+    }
+
+    /// Returns a read-only [`Cursor`] at the current position.
+    pub fn as_cursor(&self) -> Cursor<'_, T> {
+        self.grid.cursor(self.pos)
+    }
+}
+
+impl<T: PartialEq> CursorMut<'_, T> {
+    /// Pushes the run of cells starting at the cursor one step in the
+    /// specified direction and follows it, see [`Grid::push_line`].
+    pub fn push<P>(&mut self, direction: Cardinal, empty: &T, movable: P) -> bool
+    where
+        P: Fn(&T) -> bool,
+    {
+        let moved = self.grid.push_line(self.pos, direction, empty, movable);
+        if moved {
+            self.pos += Vec2::from(direction);
+        }
+        moved
+    }
+}
+
 pub fn gridify_ascii<'a>(lines: impl Iterator<Item = &'a str>) -> Grid<u8> {
     let mut cols = 0;
     let mut data = vec![];
@@ -448,4 +948,113 @@ mod tests {
         assert_eq!(grid.get(&Vec2::new(5, 0)), None);
         assert_eq!(grid.get(&Vec2::new(5, 5)), None);
     }
+
+    #[test]
+    fn grid_rotate_and_flip() {
+        let grid = gridify_ascii("ABC\nDEF".lines());
+        assert_eq!(grid.rotate_cw().to_string(), "DA\nEB\nFC\n");
+        assert_eq!(grid.rotate_ccw().to_string(), "CF\nBE\nAD\n");
+        assert_eq!(grid.flip_h().to_string(), "CBA\nFED\n");
+        assert_eq!(grid.flip_v().to_string(), "DEF\nABC\n");
+        assert_eq!(grid.transpose().to_string(), "AD\nBE\nCF\n");
+        assert_eq!(
+            grid.rotate_cw().rotate_cw().to_string(),
+            grid.flip_h().flip_v().to_string()
+        );
+    }
+
+    #[test]
+    fn grid_map_and_scale() {
+        let grid = gridify_ascii("#.O".lines());
+        assert_eq!(grid.scale_horizontal(2).to_string(), "##..OO\n");
+        let wide = grid.scale_horizontal_with(|&v| match v {
+            b'O' => *b"[]",
+            v => [v, v],
+        });
+        assert_eq!(wide.to_string(), "##..[]\n");
+        let walls = grid.map(|&v| v == b'#');
+        assert_eq!(walls.position_all(|&v| v), vec![Vec2::new(0, 0)]);
+    }
+
+    #[test]
+    fn grid_subgrid() {
+        let grid = gridify_ascii("ABCDE\nFGHIJ\nKLMNO".lines());
+        let view = grid.subgrid(Rect::from_size((1, 1), 3, 2));
+        assert_eq!(view.cols(), 3);
+        assert_eq!(view.rows(), 2);
+        assert_eq!(view[(0, 0)], b'G');
+        assert_eq!(view.get(&Vec2::new(2, 1)), Some(&b'N'));
+        assert_eq!(view.get(&Vec2::new(3, 0)), None);
+        assert_eq!(view.iter_row(1).copied().collect::<Vec<_>>(), b"LMN");
+        assert_eq!(view.iter_column(2).copied().collect::<Vec<_>>(), b"IN");
+        assert_eq!(view.to_grid().to_string(), "GHI\nLMN\n");
+    }
+
+    #[test]
+    fn grid_push_line() {
+        let mut grid = gridify_ascii("#@OO.O.#".lines());
+        let movable = |&v: &u8| v != b'#';
+        let mut cursor = grid.cursor_mut(Vec2::new(1, 0));
+        assert!(cursor.push(Cardinal::East, &b'.', movable));
+        assert_eq!(cursor.pos(), Vec2::new(2, 0));
+        assert!(cursor.push(Cardinal::East, &b'.', movable));
+        assert!(!cursor.push(Cardinal::East, &b'.', movable));
+        assert_eq!(cursor.pos(), Vec2::new(3, 0));
+        assert_eq!(grid.to_string(), "#..@OOO#\n");
+        assert!(!grid.push_line(Vec2::new(3, 0), Cardinal::North, &b'.', movable));
+
+        // Nothing to push from an empty cell, so the cursor stays put
+        // rather than stepping into the wall.
+        let mut cursor = grid.cursor_mut(Vec2::new(1, 0));
+        assert!(!cursor.push(Cardinal::West, &b'.', movable));
+        assert_eq!(cursor.pos(), Vec2::new(1, 0));
+        assert!(!grid.push_line(Vec2::new(2, 0), Cardinal::East, &b'.', movable));
+        assert_eq!(grid.to_string(), "#..@OOO#\n");
+    }
+
+    #[test]
+    fn grid_par_iter() {
+        let mut grid = gridify_ascii("ABC\nDEF".lines());
+        assert_eq!(
+            grid.par_iter_pos().collect::<Vec<_>>(),
+            grid.iter_pos().collect::<Vec<_>>()
+        );
+        assert_eq!(
+            grid.par_position_all(|&v| v != b'B'),
+            grid.position_all(|&v| v != b'B')
+        );
+        grid.par_rows_mut()
+            .for_each(|row| row.make_ascii_lowercase());
+        let rows: Vec<_> = grid.par_rows().map(|row| row.to_vec()).collect();
+        assert_eq!(rows, vec![b"abc".to_vec(), b"def".to_vec()]);
+    }
+
+    #[test]
+    fn cardinal_conversions() {
+        for dir in Cardinal::iter() {
+            let v = Vec2::from(dir);
+            assert_eq!(Cardinal::try_from(v), Ok(dir));
+            assert_eq!(Cardinal::try_from(v * 7), Ok(dir));
+            assert_eq!(Cardinal::try_from(v + v.rotate_cw()).ok(), None);
+            assert_eq!(Cardinal::try_from(dir.to_arrow()), Ok(dir));
+            assert_eq!(Cardinal::try_from(dir.to_letter()), Ok(dir));
+            assert_eq!(Cardinal::from_ascii(dir.to_letter() as u8), Some(dir));
+            assert_eq!(Cardinal::from_index(dir.index()), dir);
+            assert_eq!(Vec2::from(dir.next_clockwise()), v.rotate_cw());
+            assert_eq!(dir.turn(Turn::Right), dir.next_clockwise());
+            assert_eq!(dir.turn(Turn::Left), dir.prev_clockwise());
+            assert_eq!(dir.turn(Turn::Around), dir.reverse());
+            for other in Cardinal::iter() {
+                assert_eq!(dir.turn(dir.turn_to(other)), other);
+            }
+        }
+        assert_eq!(Cardinal::try_from(Vec2::down()), Ok(Cardinal::South));
+        assert_eq!(Cardinal::try_from(Vec2::up()), Ok(Cardinal::North));
+        assert!(Cardinal::try_from(Vec2::new(0, 0)).is_err());
+        assert!(Cardinal::try_from('x').is_err());
+
+        let mut counts = [0; 4];
+        counts[Cardinal::West] += 1;
+        assert_eq!(counts, [0, 0, 0, 1]);
+    }
 }
@@ -0,0 +1,137 @@
+use crate::Vec3;
+
+/// A dense three-dimensional grid, indexed by [`Vec3`].
+///
+/// For sparse volumes a `HashSet<Vec3>` together with
+/// [`Vec3::neighbours`] is usually a better fit.
+#[derive(Clone, Debug)]
+pub struct Grid3<T> {
+    cols: usize,
+    rows: usize,
+    layers: usize,
+    data: Vec<T>,
+}
+
+impl<T: Default + Clone> Grid3<T> {
+    pub fn new(cols: usize, rows: usize, layers: usize) -> Self {
+        Self::new_with(cols, rows, layers, T::default())
+    }
+}
+
+impl<T: Clone> Grid3<T> {
+    pub fn new_with(cols: usize, rows: usize, layers: usize, value: T) -> Self {
+        Self {
+            cols,
+            rows,
+            layers,
+            data: vec![value; cols * rows * layers],
+        }
+    }
+}
+
+impl<T> Grid3<T> {
+    #[inline]
+    pub fn cols(&self) -> usize {
+        self.cols
+    }
+
+    #[inline]
+    pub fn rows(&self) -> usize {
+        self.rows
+    }
+
+    #[inline]
+    pub fn layers(&self) -> usize {
+        self.layers
+    }
+
+    #[inline]
+    pub fn get(&self, pos: &Vec3) -> Option<&T> {
+        match self.in_bounds(pos) {
+            true => Some(&self.data[self.calc_index(pos)]),
+            false => None,
+        }
+    }
+
+    #[inline]
+    pub fn set(&mut self, pos: impl Into<Vec3>, value: T) -> T {
+        let pos = pos.into();
+        assert!(self.in_bounds(&pos));
+        let index = self.calc_index(&pos);
+        std::mem::replace(&mut self.data[index], value)
+    }
+
+    /// Returns an iterator of all the valid coordinates in the grid,
+    /// ordered by layer, then row, then column.
+    pub fn iter_pos(&self) -> impl Iterator<Item = Vec3> {
+        let (cols, rows, layers) = (self.cols as i64, self.rows as i64, self.layers as i64);
+        (0..layers).flat_map(move |z| {
+            (0..rows).flat_map(move |y| (0..cols).map(move |x| Vec3::new(x, y, z)))
+        })
+    }
+
+    /// Returns the face-adjacent neighbours of `pos` which are inside
+    /// the grid.
+    pub fn neighbours(&self, pos: Vec3) -> impl Iterator<Item = Vec3> + '_ {
+        pos.neighbours().filter(|n| self.in_bounds(n))
+    }
+
+    pub fn in_bounds(&self, &Vec3 { x, y, z }: &Vec3) -> bool {
+        (x >= 0 && x < self.cols as i64)
+            && (y >= 0 && y < self.rows as i64)
+            && (z >= 0 && z < self.layers as i64)
+    }
+
+    #[inline]
+    fn calc_index(&self, &Vec3 { x, y, z }: &Vec3) -> usize {
+        ((z * self.rows as i64 + y) * self.cols as i64 + x) as usize
+    }
+}
+
+impl<T> std::ops::Index<Vec3> for Grid3<T> {
+    type Output = T;
+
+    #[inline]
+    fn index(&self, pos: Vec3) -> &Self::Output {
+        assert!(
+            self.in_bounds(&pos),
+            "{pos} not in grid bounded by (0, 0, 0) -> ({}, {}, {})",
+            self.cols,
+            self.rows,
+            self.layers
+        );
+        &self.data[self.calc_index(&pos)]
+    }
+}
+
+impl<T> std::ops::IndexMut<Vec3> for Grid3<T> {
+    fn index_mut(&mut self, pos: Vec3) -> &mut Self::Output {
+        assert!(
+            self.in_bounds(&pos),
+            "{pos} not in grid bounded by (0, 0, 0) -> ({}, {}, {})",
+            self.cols,
+            self.rows,
+            self.layers
+        );
+        let index = self.calc_index(&pos);
+        &mut self.data[index]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn grid3() {
+        let mut grid: Grid3<u8> = Grid3::new(3, 2, 4);
+        grid.set((2, 1, 3), 7);
+        grid[Vec3::new(0, 0, 1)] = 1;
+        assert_eq!(grid.get(&Vec3::new(2, 1, 3)), Some(&7));
+        assert_eq!(grid.get(&Vec3::new(3, 1, 3)), None);
+        assert_eq!(grid.iter_pos().count(), 24);
+        assert_eq!(grid.iter_pos().last(), Some(Vec3::new(2, 1, 3)));
+        assert_eq!(grid.neighbours(Vec3::new(0, 0, 0)).count(), 3);
+        assert_eq!(grid.iter_pos().filter(|&p| grid[p] != 0).count(), 2);
+    }
+}
@@ -0,0 +1,104 @@
+use std::collections::{HashMap, HashSet};
+use std::hash::{BuildHasherDefault, Hasher};
+
+/// A `HashMap` using [`FastHasher`].
+pub type FastMap<K, V> = HashMap<K, V, BuildHasherDefault<FastHasher>>;
+
+/// A `HashSet` using [`FastHasher`].
+pub type FastSet<T> = HashSet<T, BuildHasherDefault<FastHasher>>;
+
+/// The word-at-a-time multiplicative hash used by rustc (FxHash).
+///
+/// Much faster than the default SipHash for small keys such as [`Vec2`]
+/// or integer arrays, but offers no protection against collision
+/// attacks, which is not a concern for puzzle input.
+///
+/// [`Vec2`]: crate::Vec2
+#[derive(Clone, Copy, Debug, Default)]
+pub struct FastHasher {
+    hash: u64,
+}
+
+const SEED: u64 = 0x51_7c_c1_b7_27_22_0a_95;
+
+impl FastHasher {
+    #[inline]
+    fn add(&mut self, word: u64) {
+        self.hash = (self.hash.rotate_left(5) ^ word).wrapping_mul(SEED);
+    }
+}
+
+impl Hasher for FastHasher {
+    #[inline]
+    fn write(&mut self, bytes: &[u8]) {
+        let mut chunks = bytes.chunks_exact(8);
+        for chunk in &mut chunks {
+            self.add(u64::from_le_bytes(chunk.try_into().unwrap()));
+        }
+        let rest = chunks.remainder();
+        if !rest.is_empty() {
+            let mut word = [0; 8];
+            word[..rest.len()].copy_from_slice(rest);
+            self.add(u64::from_le_bytes(word));
+        }
+    }
+
+    #[inline]
+    fn write_u8(&mut self, i: u8) {
+        self.add(i as u64);
+    }
+
+    #[inline]
+    fn write_u16(&mut self, i: u16) {
+        self.add(i as u64);
+    }
+
+    #[inline]
+    fn write_u32(&mut self, i: u32) {
+        self.add(i as u64);
+    }
+
+    #[inline]
+    fn write_u64(&mut self, i: u64) {
+        self.add(i);
+    }
+
+    #[inline]
+    fn write_usize(&mut self, i: usize) {
+        self.add(i as u64);
+    }
+
+    #[inline]
+    fn finish(&self) -> u64 {
+        self.hash
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Vec2;
+    use std::hash::BuildHasher;
+
+    #[test]
+    fn fast_collections() {
+        let build = BuildHasherDefault::<FastHasher>::default();
+        assert_eq!(
+            build.hash_one(Vec2::new(1, 2)),
+            build.hash_one(Vec2::new(1, 2))
+        );
+        assert_ne!(
+            build.hash_one(Vec2::new(1, 2)),
+            build.hash_one(Vec2::new(2, 1))
+        );
+        assert_ne!(build.hash_one("abcdefghi"), build.hash_one("abcdefghj"));
+
+        let mut map: FastMap<[i16; 4], i16> = FastMap::default();
+        *map.entry([-1, 0, 2, 1]).or_default() += 3;
+        *map.entry([-1, 0, 2, 1]).or_default() += 4;
+        assert_eq!(map[&[-1, 0, 2, 1]], 7);
+
+        let set: FastSet<_> = (0..100).map(|n| n % 7).collect();
+        assert_eq!(set.len(), 7);
+    }
+}
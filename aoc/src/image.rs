@@ -0,0 +1,277 @@
+use crate::Grid;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+/// An RGB colour.
+pub type Rgb = [u8; 3];
+
+impl<T> Grid<T> {
+    /// Writes the grid as a binary PPM image with one pixel per cell.
+    pub fn write_ppm<W, F>(&self, mut w: W, colour: F) -> io::Result<()>
+    where
+        W: Write,
+        F: Fn(&T) -> Rgb,
+    {
+        write!(w, "P6\n{} {}\n255\n", self.cols(), self.rows())?;
+        for pos in self.iter_pos() {
+            w.write_all(&colour(&self[pos]))?;
+        }
+        Ok(())
+    }
+
+    /// Writes the grid as an uncompressed PNG image with one pixel per
+    /// cell.
+    pub fn write_png<W, F>(&self, mut w: W, colour: F) -> io::Result<()>
+    where
+        W: Write,
+        F: Fn(&T) -> Rgb,
+    {
+        let mut raw = Vec::with_capacity(self.rows() * (self.cols() * 3 + 1));
+        for y in 0..self.rows() {
+            raw.push(0); // filter: none
+            for value in self.iter_row(y) {
+                raw.extend_from_slice(&colour(value));
+            }
+        }
+
+        let mut header = vec![];
+        header.extend_from_slice(&(self.cols() as u32).to_be_bytes());
+        header.extend_from_slice(&(self.rows() as u32).to_be_bytes());
+        header.extend_from_slice(&[8, 2, 0, 0, 0]);
+
+        w.write_all(b"\x89PNG\r\n\x1a\n")?;
+        write_chunk(&mut w, b"IHDR", &header)?;
+        write_chunk(&mut w, b"IDAT", &zlib_stored(&raw))?;
+        write_chunk(&mut w, b"IEND", &[])
+    }
+
+    /// Writes the grid as an SVG image with one unit square per cell.
+    pub fn write_svg<W, F>(&self, mut w: W, colour: F) -> io::Result<()>
+    where
+        W: Write,
+        F: Fn(&T) -> Rgb,
+    {
+        writeln!(
+            w,
+            r#"<svg xmlns="http://www.w3.org/2000/svg" viewBox="0 0 {0} {1}" width="{2}" height="{3}" shape-rendering="crispEdges">"#,
+            self.cols(),
+            self.rows(),
+            self.cols() * 8,
+            self.rows() * 8,
+        )?;
+        for pos in self.iter_pos() {
+            let [r, g, b] = colour(&self[pos]);
+            writeln!(
+                w,
+                r##"<rect x="{}" y="{}" width="1" height="1" fill="#{r:02x}{g:02x}{b:02x}"/>"##,
+                pos.x, pos.y
+            )?;
+        }
+        writeln!(w, "</svg>")
+    }
+
+    /// Saves the grid as an image, choosing the format from the file
+    /// extension of `path` (`ppm`, `png` or `svg`).
+    pub fn save_image<F>(&self, path: impl AsRef<Path>, colour: F) -> io::Result<()>
+    where
+        F: Fn(&T) -> Rgb,
+    {
+        let path = path.as_ref();
+        ImageFormat::from_path(path)?.save(self, path, colour)
+    }
+}
+
+/// Supported image formats.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ImageFormat {
+    Ppm,
+    Png,
+    Svg,
+}
+
+impl ImageFormat {
+    fn from_path(path: &Path) -> io::Result<Self> {
+        match path.extension().and_then(|ext| ext.to_str()) {
+            Some("ppm") => Ok(Self::Ppm),
+            Some("png") => Ok(Self::Png),
+            Some("svg") => Ok(Self::Svg),
+            _ => Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("unsupported image format: {}", path.display()),
+            )),
+        }
+    }
+
+    fn extension(self) -> &'static str {
+        match self {
+            Self::Ppm => "ppm",
+            Self::Png => "png",
+            Self::Svg => "svg",
+        }
+    }
+
+    fn write<T, W, F>(self, grid: &Grid<T>, w: W, colour: F) -> io::Result<()>
+    where
+        W: Write,
+        F: Fn(&T) -> Rgb,
+    {
+        match self {
+            Self::Ppm => grid.write_ppm(w, colour),
+            Self::Png => grid.write_png(w, colour),
+            Self::Svg => grid.write_svg(w, colour),
+        }
+    }
+
+    /// Writes the grid to a new file at `path`, flushing it so that write
+    /// errors are reported rather than lost when the buffer is dropped.
+    fn save<T, F>(self, grid: &Grid<T>, path: &Path, colour: F) -> io::Result<()>
+    where
+        F: Fn(&T) -> Rgb,
+    {
+        let mut file = io::BufWriter::new(std::fs::File::create(path)?);
+        self.write(grid, &mut file, colour)?;
+        file.flush()
+    }
+}
+
+/// Writes a sequence of numbered image frames to a directory.
+#[derive(Debug)]
+pub struct FrameWriter {
+    dir: PathBuf,
+    format: ImageFormat,
+    next: usize,
+}
+
+impl FrameWriter {
+    /// Creates a new `FrameWriter`, creating `dir` if it does not exist.
+    pub fn new(dir: impl Into<PathBuf>, format: ImageFormat) -> io::Result<Self> {
+        let dir = dir.into();
+        std::fs::create_dir_all(&dir)?;
+        Ok(Self {
+            dir,
+            format,
+            next: 0,
+        })
+    }
+
+    /// Returns the number of frames written so far.
+    pub fn frames(&self) -> usize {
+        self.next
+    }
+
+    /// Writes the next frame, returning the path of the new file.
+    pub fn write<T, F>(&mut self, grid: &Grid<T>, colour: F) -> io::Result<PathBuf>
+    where
+        F: Fn(&T) -> Rgb,
+    {
+        let path = self.dir.join(format!(
+            "frame-{:06}.{}",
+            self.next,
+            self.format.extension()
+        ));
+        self.format.save(grid, &path, colour)?;
+        self.next += 1;
+        Ok(path)
+    }
+}
+
+fn write_chunk(mut w: impl Write, kind: &[u8; 4], data: &[u8]) -> io::Result<()> {
+    w.write_all(&(data.len() as u32).to_be_bytes())?;
+    w.write_all(kind)?;
+    w.write_all(data)?;
+    let crc = crc32(kind.iter().chain(data));
+    w.write_all(&crc.to_be_bytes())
+}
+
+/// Wraps `data` in a zlib stream made of uncompressed deflate blocks.
+fn zlib_stored(data: &[u8]) -> Vec<u8> {
+    let mut out = vec![0x78, 0x01];
+    let mut blocks = data.chunks(u16::MAX as usize).peekable();
+    if blocks.peek().is_none() {
+        out.extend_from_slice(&[1, 0, 0, 0xff, 0xff]);
+    }
+    while let Some(block) = blocks.next() {
+        let len = block.len() as u16;
+        out.push(blocks.peek().is_none() as u8);
+        out.extend_from_slice(&len.to_le_bytes());
+        out.extend_from_slice(&(!len).to_le_bytes());
+        out.extend_from_slice(block);
+    }
+    out.extend_from_slice(&adler32(data).to_be_bytes());
+    out
+}
+
+fn crc32<'a>(bytes: impl IntoIterator<Item = &'a u8>) -> u32 {
+    let mut crc = !0u32;
+    for &byte in bytes {
+        crc ^= byte as u32;
+        for _ in 0..8 {
+            crc = (crc >> 1) ^ (0xedb8_8320 & (crc & 1).wrapping_neg());
+        }
+    }
+    !crc
+}
+
+fn adler32(bytes: &[u8]) -> u32 {
+    let (mut a, mut b) = (1u32, 0u32);
+    for &byte in bytes {
+        a = (a + byte as u32) % 65521;
+        b = (b + a) % 65521;
+    }
+    (b << 16) | a
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gridify_ascii;
+
+    fn colour(&v: &u8) -> Rgb {
+        match v {
+            b'#' => [255, 255, 255],
+            _ => [0, 0, 0],
+        }
+    }
+
+    #[test]
+    fn checksums() {
+        assert_eq!(crc32(b"IEND"), 0xae42_6082);
+        assert_eq!(adler32(b"Wikipedia"), 0x11e6_0398);
+    }
+
+    #[test]
+    fn export_images() {
+        let grid = gridify_ascii("#.\n.#".lines());
+
+        let mut ppm = vec![];
+        grid.write_ppm(&mut ppm, colour).unwrap();
+        assert_eq!(&ppm[..11], b"P6\n2 2\n255\n");
+        assert_eq!(
+            &ppm[11..],
+            &[255, 255, 255, 0, 0, 0, 0, 0, 0, 255, 255, 255]
+        );
+
+        let mut png = vec![];
+        grid.write_png(&mut png, colour).unwrap();
+        assert_eq!(&png[..8], b"\x89PNG\r\n\x1a\n");
+        assert_eq!(&png[12..16], b"IHDR");
+        assert_eq!(&png[16..24], &[0, 0, 0, 2, 0, 0, 0, 2]);
+        assert_eq!(&png[png.len() - 12..], b"\0\0\0\0IEND\xae\x42\x60\x82");
+
+        let mut svg = vec![];
+        grid.write_svg(&mut svg, colour).unwrap();
+        let svg = String::from_utf8(svg).unwrap();
+        let lines: Vec<_> = svg.lines().collect();
+        assert_eq!(lines.len(), 6);
+        assert!(lines[0].starts_with("<svg ") && lines[0].contains(r#"viewBox="0 0 2 2""#));
+        assert_eq!(
+            lines[2],
+            r##"<rect x="1" y="0" width="1" height="1" fill="#000000"/>"##
+        );
+        assert_eq!(
+            lines[4],
+            r##"<rect x="1" y="1" width="1" height="1" fill="#ffffff"/>"##
+        );
+        assert_eq!(lines[5], "</svg>");
+    }
+}
@@ -0,0 +1,180 @@
+use std::borrow::Borrow;
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// Maps labels, such as `&str` names, to dense `u32` ids and back.
+///
+/// Ids are assigned in the order labels are first interned, so data
+/// about each label can be kept in a [`Table`] instead of a `HashMap`.
+#[derive(Clone, Debug)]
+pub struct Interner<T> {
+    ids: HashMap<T, u32>,
+    labels: Vec<T>,
+}
+
+impl<T> Default for Interner<T> {
+    fn default() -> Self {
+        Self {
+            ids: HashMap::new(),
+            labels: vec![],
+        }
+    }
+}
+
+impl<T: Hash + Eq + Clone> Interner<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the id of `label`, assigning the next id if it has not
+    /// been seen before.
+    pub fn intern(&mut self, label: T) -> u32 {
+        if let Some(&id) = self.ids.get(&label) {
+            return id;
+        }
+        let id = u32::try_from(self.labels.len()).expect("too many labels");
+        self.ids.insert(label.clone(), id);
+        self.labels.push(label);
+        id
+    }
+
+    /// Returns the id of `label` if it has been interned.
+    pub fn id<Q>(&self, label: &Q) -> Option<u32>
+    where
+        T: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.ids.get(label).copied()
+    }
+}
+
+impl<T> Interner<T> {
+    #[inline]
+    pub fn label(&self, id: u32) -> &T {
+        &self.labels[id as usize]
+    }
+
+    /// Returns the labels, indexed by id.
+    #[inline]
+    pub fn labels(&self) -> &[T] {
+        &self.labels
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.labels.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.labels.is_empty()
+    }
+
+    /// Returns an iterator over the ids and their labels.
+    pub fn iter(&self) -> impl Iterator<Item = (u32, &T)> {
+        (0..).zip(&self.labels)
+    }
+
+    /// Creates a table with an entry for every id, each set to `value`.
+    pub fn table<V: Clone>(&self, value: V) -> Table<V> {
+        Table(vec![value; self.len()])
+    }
+
+    /// Creates a table with an entry for every id, computed from the id
+    /// and its label.
+    pub fn table_with<V>(&self, f: impl FnMut((u32, &T)) -> V) -> Table<V> {
+        Table(self.iter().map(f).collect())
+    }
+}
+
+/// A `Vec` indexed by the `u32` ids from an [`Interner`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Table<V>(Vec<V>);
+
+impl<V> Table<V> {
+    pub fn new() -> Self {
+        Self(vec![])
+    }
+
+    /// Appends a value, returning its id.
+    pub fn push(&mut self, value: V) -> u32 {
+        let id = u32::try_from(self.0.len()).expect("too many values");
+        self.0.push(value);
+        id
+    }
+
+    #[inline]
+    pub fn get(&self, id: u32) -> Option<&V> {
+        self.0.get(id as usize)
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns an iterator over the ids and their values.
+    pub fn iter(&self) -> impl Iterator<Item = (u32, &V)> {
+        (0..).zip(&self.0)
+    }
+
+    pub fn values(&self) -> &[V] {
+        &self.0
+    }
+
+    pub fn values_mut(&mut self) -> &mut [V] {
+        &mut self.0
+    }
+}
+
+impl<V> From<Vec<V>> for Table<V> {
+    fn from(values: Vec<V>) -> Self {
+        Self(values)
+    }
+}
+
+impl<V> std::ops::Index<u32> for Table<V> {
+    type Output = V;
+
+    #[inline]
+    fn index(&self, id: u32) -> &Self::Output {
+        &self.0[id as usize]
+    }
+}
+
+impl<V> std::ops::IndexMut<u32> for Table<V> {
+    #[inline]
+    fn index_mut(&mut self, id: u32) -> &mut Self::Output {
+        &mut self.0[id as usize]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn intern_labels() {
+        let mut wires = Interner::new();
+        let ids: Vec<_> = ["x00", "y00", "x00", "z00"].map(|w| wires.intern(w)).into();
+        assert_eq!(ids, [0, 1, 0, 2]);
+        assert_eq!(wires.len(), 3);
+        assert_eq!(wires.id("z00"), Some(2));
+        assert_eq!(wires.id("z01"), None);
+        assert_eq!(wires.label(1), &"y00");
+
+        let mut values = wires.table_with(|(_, wire)| wire.starts_with('x'));
+        assert_eq!(values.values(), [true, false, false]);
+        values[2] = true;
+        assert_eq!(values.iter().filter(|(_, &v)| v).count(), 2);
+        let mut counts = wires.table(0);
+        counts[wires.id("x00").unwrap()] += 1;
+        assert_eq!(counts.get(3), None);
+        assert_eq!(counts.push(5), 3);
+    }
+}
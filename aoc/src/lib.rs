@@ -1,12 +1,51 @@
+mod bits;
+pub mod combinator;
+pub mod cycle;
+pub mod graph;
 mod grid;
+mod grid3;
+mod hash;
+mod image;
+mod intern;
+pub mod math;
+pub mod memo;
+mod num;
+mod overlay;
+mod parse;
+pub mod ranges;
+mod record;
+mod rect;
+mod region;
+mod render;
+mod scan;
+mod section;
 mod vec2;
+mod vec3;
+pub mod viz;
 
-pub use grid::{gridify_ascii, Cardinal, Cursor, Grid};
+pub use bits::{BitGrid, BitSet};
+pub use grid::{gridify_ascii, Cardinal, Cursor, CursorMut, Grid, SubGrid, Turn};
+pub use grid3::Grid3;
+pub use hash::{FastHasher, FastMap, FastSet};
+pub use image::{FrameWriter, ImageFormat, Rgb};
+pub use intern::{Interner, Table};
+pub use num::{Int, Signed};
+pub use overlay::Overlay;
+pub use parse::{ints, ints_n, try_parse, uints, ParseError};
+#[doc(hidden)]
+pub use parse::{try_parse_pair, try_parse_token};
 pub use rayon;
+pub use record::{fingerprint, record, record_answer};
+pub use rect::Rect;
 pub use regex;
+pub use region::Region;
+pub use render::{Colour, Render};
+pub use scan::Pattern;
+pub use section::{sections, Section, Sections};
 pub use std::borrow::Cow;
 use std::io::Write;
-pub use vec2::Vec2;
+pub use vec2::{Vec2, Vec2T};
+pub use vec3::{Vec3, Vec3T};
 
 pub trait AllPairs<T> {
     fn all_pairs<'a>(&'a self) -> impl Iterator<Item = (&'a T, &'a T)>
@@ -57,6 +96,22 @@ macro_rules! time {
 
 #[macro_export]
 macro_rules! test {
+    ($m:ident, $name:ident, $parser:ident ?, $func:ident, $expect:expr) => {
+        #[test]
+        fn $name() {
+            const INPUT: &str = include_str!(concat!("../examples/", stringify!($m), ".in"));
+            let parsed = super::$parser(INPUT).unwrap_or_else(|err| panic!("parse error: {err}"));
+            assert_eq!(super::$func(&parsed).to_string(), ($expect).to_string());
+        }
+    };
+    ($m:ident, $name: ident, $parser:ident ?, $f:literal, $func:ident, $expect:expr) => {
+        #[test]
+        fn $name() {
+            const INPUT: &str = include_str!(concat!("../examples/", $f));
+            let parsed = super::$parser(INPUT).unwrap_or_else(|err| panic!("parse error: {err}"));
+            assert_eq!(super::$func(&parsed).to_string(), ($expect).to_string());
+        }
+    };
     ($m:ident, $name:ident, $parser:ident, $func:ident, $expect:expr) => {
         #[test]
         fn $name() {
@@ -89,11 +144,18 @@ macro_rules! test {
     };
 }
 
+/// Loads the puzzle input from the command line, a file, stdin or
+/// `fallback`.
+///
+/// The input is normalised by stripping a byte order mark, converting
+/// `\r\n` line endings to `\n` and removing whitespace from the end of
+/// the input. Also returns the text to print before the next line of
+/// output and the [`fingerprint`] of the trimmed input.
 #[inline(never)]
-pub fn load(fallback: &'static str) -> (Cow<'static, str>, &'static str) {
+pub fn load(fallback: &'static str) -> (Cow<'static, str>, &'static str, u64) {
     const MSG: &str = "puzzle input (finish with ^D): ";
     let mut arguments = std::env::args().skip(1);
-    match arguments.next().as_deref() {
+    let (input, pre) = match arguments.next().as_deref() {
         Some("-") => {
             print!("{}", MSG);
             std::io::stdout().flush().unwrap();
@@ -102,11 +164,7 @@ pub fn load(fallback: &'static str) -> (Cow<'static, str>, &'static str) {
         }
         Some("-i") | Some("--input") => {
             // Concatenate all following arguments and use as input.
-            let mut input = String::new();
-            for argument in arguments {
-                input.push(' ');
-                input.push_str(&argument);
-            }
+            let input = arguments.collect::<Vec<_>>().join(" ");
             (Cow::Owned(input), "")
         }
         Some(path) => {
@@ -122,26 +180,91 @@ pub fn load(fallback: &'static str) -> (Cow<'static, str>, &'static str) {
             let input = std::io::read_to_string(std::io::stdin()).unwrap();
             (Cow::Owned(input), "\n")
         }
+    };
+    let input = normalise(input);
+    let fingerprint = fingerprint(input.trim());
+    (input, pre, fingerprint)
+}
+
+fn normalise(input: Cow<'static, str>) -> Cow<'static, str> {
+    let text = input.strip_prefix('\u{feff}').unwrap_or(&input).trim_end();
+    match text.contains('\r') {
+        true => Cow::Owned(text.replace("\r\n", "\n")),
+        false if text.len() == input.len() => input,
+        false => Cow::Owned(text.to_string()),
     }
 }
 
 #[macro_export]
 macro_rules! setup {
+    (@record $m:ident, $fingerprint:ident, $part:ident, $elapsed:ident) => {
+        $crate::record_answer(
+            stringify!($m),
+            $fingerprint,
+            stringify!($part),
+            &$part,
+            $elapsed,
+        );
+    };
+    ($m:ident, $parser:ident : $ty:ty = $expr:expr; $($rest:tt)*) => {
+        fn $parser(input: &str) -> Result<$ty, $crate::ParseError> {
+            use $crate::combinator::*;
+            parse_all($expr, input)
+        }
+
+        $crate::setup!($m, $parser?; $($rest)*);
+    };
+    ($m:ident, $parser:ident ?; $($f1:literal:)? $part1:ident == $e1:expr, $($f2:literal:)? $part2:ident == $e2:expr) => {
+        fn main() {
+            eprintln!("{}", stringify!($m));
+            const INPUT: &str = include_str!(concat!("../input/", stringify!($m), ".in"));
+            let (input, pre, fingerprint) = $crate::load(INPUT);
+            let trimmed_input = input.trim();
+            eprintln!("{pre}input: {fingerprint:016x}");
+
+            let (parsed, elapsed_parse) = aoc::time!($parser(trimmed_input));
+            let parsed = match parsed {
+                Ok(parsed) => parsed,
+                Err(err) => {
+                    eprintln!("parse error: {err}");
+                    std::process::exit(1);
+                }
+            };
+            eprintln!("parse ({elapsed_parse:?})");
+
+            let (part1, elapsed_part1) = aoc::time!($part1(&parsed).to_string());
+            eprintln!("part1: {part1} ({elapsed_part1:?})");
+            $crate::setup!(@record $m, fingerprint, part1, elapsed_part1);
+
+            let (part2, elapsed_part2) = aoc::time!($part2(&parsed).to_string());
+            eprintln!("part2: {part2} ({elapsed_part2:?})");
+            $crate::setup!(@record $m, fingerprint, part2, elapsed_part2);
+        }
+
+        #[cfg(test)]
+        mod $m {
+            $crate::test!($m, part1_example, $parser?, $($f1,)? $part1, $e1);
+            $crate::test!($m, part2_example, $parser?, $($f2,)? $part2, $e2);
+        }
+    };
     ($m:ident, $parser:ident; $($f1:literal:)? $part1:ident == $e1:expr, $($f2:literal:)? $part2:ident == $e2:expr) => {
         fn main() {
             eprintln!("{}", stringify!($m));
             const INPUT: &str = include_str!(concat!("../input/", stringify!($m), ".in"));
-            let (input, pre) = $crate::load(INPUT);
+            let (input, pre, fingerprint) = $crate::load(INPUT);
             let trimmed_input = input.trim();
+            eprintln!("{pre}input: {fingerprint:016x}");
 
             let (parsed, elapsed_parse) = aoc::time!($parser(trimmed_input));
-            eprintln!("{pre}parse ({elapsed_parse:?})");
+            eprintln!("parse ({elapsed_parse:?})");
 
-            let (part1, elapsed_part1) = aoc::time!($part1(&parsed));
+            let (part1, elapsed_part1) = aoc::time!($part1(&parsed).to_string());
             eprintln!("part1: {part1} ({elapsed_part1:?})");
+            $crate::setup!(@record $m, fingerprint, part1, elapsed_part1);
 
-            let (part2, elapsed_part2) = aoc::time!($part2(&parsed));
+            let (part2, elapsed_part2) = aoc::time!($part2(&parsed).to_string());
             eprintln!("part2: {part2} ({elapsed_part2:?})");
+            $crate::setup!(@record $m, fingerprint, part2, elapsed_part2);
         }
 
         #[cfg(test)]
@@ -154,14 +277,17 @@ macro_rules! setup {
         fn main() {
             eprintln!("{}", stringify!($m));
             const INPUT: &str = include_str!(concat!("../input/", stringify!($m), ".in"));
-            let (input, pre) = $crate::load(INPUT);
+            let (input, pre, fingerprint) = $crate::load(INPUT);
             let trimmed_input = input.trim();
+            eprintln!("{pre}input: {fingerprint:016x}");
 
-            let (part1, elapsed_part1) = aoc::time!($part1(trimmed_input));
-            eprintln!("{pre}part1: {part1} ({elapsed_part1:?})");
+            let (part1, elapsed_part1) = aoc::time!($part1(trimmed_input).to_string());
+            eprintln!("part1: {part1} ({elapsed_part1:?})");
+            $crate::setup!(@record $m, fingerprint, part1, elapsed_part1);
 
-            let (part2, elapsed_part2) = aoc::time!($part2(trimmed_input));
+            let (part2, elapsed_part2) = aoc::time!($part2(trimmed_input).to_string());
             eprintln!("part2: {part2} ({elapsed_part2:?})");
+            $crate::setup!(@record $m, fingerprint, part2, elapsed_part2);
         }
 
         #[cfg(test)]
@@ -174,11 +300,12 @@ macro_rules! setup {
         fn main() {
             eprintln!("{}", stringify!($m));
             const INPUT: &str = include_str!(concat!("../input/", stringify!($m), ".in"));
-            let (input, pre) = $crate::load(INPUT);
+            let (input, pre, fingerprint) = $crate::load(INPUT);
             let trimmed_input = input.trim();
+            eprintln!("{pre}input: {fingerprint:016x}");
 
             let (parsed, elapsed_parse) = aoc::time!($parser(trimmed_input));
-            eprintln!("{pre}parse ({elapsed_parse:?})");
+            eprintln!("parse ({elapsed_parse:?})");
             eprintln!("{parsed:?}");
         }
     };
@@ -186,8 +313,9 @@ macro_rules! setup {
         fn main() {
             eprintln!("{}", stringify!($m));
             const INPUT: &str = include_str!(concat!("../input/", stringify!($m), ".in"));
-            let (input, pre) = $crate::load(INPUT);
+            let (input, pre, fingerprint) = $crate::load(INPUT);
             let trimmed_input = input.trim();
+            eprintln!("{pre}input: {fingerprint:016x}");
             eprintln!("{trimmed_input}");
         }
     };
@@ -217,6 +345,36 @@ macro_rules! parse_list {
     };
 }
 
+/// Like [`parse_list!`], but returns a `Result<Vec<_>, ParseError>`
+/// with the column of the first token that failed to parse.
+#[macro_export]
+macro_rules! try_parse_list {
+    ($line:expr) => {{
+        let line: &str = &$line;
+        line.split_whitespace()
+            .map(|token| $crate::try_parse_token(line, token))
+            .collect::<Result<Vec<_>, $crate::ParseError>>()
+    }};
+    ($line:expr, $ty:ty) => {{
+        let line: &str = &$line;
+        line.split_whitespace()
+            .map(|token| $crate::try_parse_token(line, token))
+            .collect::<Result<Vec<$ty>, $crate::ParseError>>()
+    }};
+    ($line:expr, $pat:literal) => {{
+        let line: &str = &$line;
+        line.split($pat)
+            .map(|token| $crate::try_parse_token(line, token))
+            .collect::<Result<Vec<_>, $crate::ParseError>>()
+    }};
+    ($line:expr, $pat:literal, $ty:ty) => {{
+        let line: &str = &$line;
+        line.split($pat)
+            .map(|token| $crate::try_parse_token(line, token))
+            .collect::<Result<Vec<$ty>, $crate::ParseError>>()
+    }};
+}
+
 #[macro_export]
 macro_rules! take_lists {
     ($iter:expr, $pat:literal) => {
@@ -293,6 +451,44 @@ macro_rules! take_pairs {
     };
 }
 
+/// Like [`take_pairs!`], but yields a `Result` for each line, reporting
+/// missing or extra elements as well as values that failed to parse.
+///
+/// Line numbers count from the first line taken from the iterator.
+#[macro_export]
+macro_rules! try_take_pairs {
+    ($iter:expr, $pat:literal) => {
+        $iter.enumerate().map_while(|(index, line)| {
+            let line: &str = &line;
+            if line.trim().is_empty() {
+                return None;
+            }
+            let parsed = $crate::try_parse_pair(line, line.trim().split($pat));
+            Some(parsed.map_err(|err| err.at_line(index + 1)))
+        })
+    };
+    ($iter:expr, $ty:ty) => {
+        $iter.enumerate().map_while(|(index, line)| {
+            let line: &str = &line;
+            if line.trim().is_empty() {
+                return None;
+            }
+            let parsed = $crate::try_parse_pair::<$ty, $ty>(line, line.split_whitespace());
+            Some(parsed.map_err(|err| err.at_line(index + 1)))
+        })
+    };
+    ($iter:expr) => {
+        $iter.enumerate().map_while(|(index, line)| {
+            let line: &str = &line;
+            if line.trim().is_empty() {
+                return None;
+            }
+            let parsed = $crate::try_parse_pair(line, line.split_whitespace());
+            Some(parsed.map_err(|err| err.at_line(index + 1)))
+        })
+    };
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -316,4 +512,37 @@ mod tests {
         assert_eq!(pairs.next(), Some((4, 5)));
         assert_eq!(pairs.next(), None);
     }
+
+    #[test]
+    fn fallible_parsing() {
+        let values = try_parse_list!("1,2,x", ',', u8);
+        let err = values.unwrap_err();
+        assert_eq!((err.line, err.column, err.text.as_str()), (1, 5, "x"));
+        assert_eq!(try_parse_list!("3 4", u8), Ok(vec![3, 4]));
+        assert!(try_parse::<u8>("256").is_err());
+
+        let mut pairs = try_take_pairs!("1|2\n3|4|5\n6|y".lines(), '|');
+        assert_eq!(pairs.next(), Some(Ok((1, 2))));
+        let err: ParseError = pairs.next().unwrap().unwrap_err();
+        assert_eq!((err.line, err.column, err.text.as_str()), (2, 5, "5"));
+        let err = pairs.next().unwrap().unwrap_err();
+        assert_eq!((err.line, err.column), (3, 3));
+        assert_eq!(
+            err.to_string(),
+            "line 3, column 3: invalid digit found in string (in \"y\")"
+        );
+
+        let mut pairs = try_take_pairs!("7".lines(), i32);
+        assert_eq!(pairs.next().unwrap().unwrap_err().column, 2);
+    }
+
+    #[test]
+    fn normalise_input() {
+        let input = normalise(Cow::Borrowed("\u{feff}a\r\nb\t\r\n \t\r\n c \n\n"));
+        assert_eq!(input, "a\nb\t\n \t\n c");
+        assert!(matches!(
+            normalise(Cow::Borrowed("a\n\nb")),
+            Cow::Borrowed(_)
+        ));
+    }
 }
@@ -0,0 +1,178 @@
+//! Number theory and exact integer linear algebra.
+//!
+//! Everything is generic over [`Int`] or [`Signed`]. Intermediate
+//! products must fit in the integer type, so use `i128` for large
+//! moduli.
+
+use crate::{Int, Signed};
+
+/// Returns the non-negative greatest common divisor of `a` and `b`.
+pub fn gcd<T: Int>(mut a: T, mut b: T) -> T {
+    while b != T::ZERO {
+        (a, b) = (b, a % b);
+    }
+    if a < T::ZERO {
+        T::ZERO - a
+    } else {
+        a
+    }
+}
+
+/// Returns the non-negative least common multiple of `a` and `b`.
+pub fn lcm<T: Int>(a: T, b: T) -> T {
+    if a == T::ZERO || b == T::ZERO {
+        return T::ZERO;
+    }
+    let lcm = a / gcd(a, b) * b;
+    if lcm < T::ZERO {
+        T::ZERO - lcm
+    } else {
+        lcm
+    }
+}
+
+/// Extended Euclidean algorithm: returns `(g, x, y)` such that
+/// `a * x + b * y == g`, where `g` is the greatest common divisor.
+pub fn egcd<T: Signed>(a: T, b: T) -> (T, T, T) {
+    let (mut r0, mut r1) = (a, b);
+    let (mut x0, mut x1) = (T::ONE, T::ZERO);
+    let (mut y0, mut y1) = (T::ZERO, T::ONE);
+    while r1 != T::ZERO {
+        let q = r0 / r1;
+        (r0, r1) = (r1, r0 - q * r1);
+        (x0, x1) = (x1, x0 - q * x1);
+        (y0, y1) = (y1, y0 - q * y1);
+    }
+    match r0 < T::ZERO {
+        true => (-r0, -x0, -y0),
+        false => (r0, x0, y0),
+    }
+}
+
+/// Returns the inverse of `a` modulo `m`, if `a` and `m` are coprime.
+pub fn mod_inv<T: Signed>(a: T, m: T) -> Option<T> {
+    let (g, x, _) = egcd(a.rem_euclid(m), m);
+    (g == T::ONE).then(|| x.rem_euclid(m))
+}
+
+/// Returns `base` raised to `exp` modulo `m`.
+pub fn mod_pow<T: Int>(base: T, mut exp: u64, m: T) -> T {
+    let mut base = base.rem_euclid(m);
+    let mut result = T::ONE.rem_euclid(m);
+    while exp > 0 {
+        if exp & 1 == 1 {
+            result = (result * base).rem_euclid(m);
+        }
+        base = (base * base).rem_euclid(m);
+        exp >>= 1;
+    }
+    result
+}
+
+/// Solves a system of congruences `x ≡ a (mod m)`, returning `(x, m)`
+/// where `x` is the smallest non-negative solution and `m` is the least
+/// common multiple of the moduli.
+///
+/// The moduli need not be coprime; `None` is returned if the
+/// congruences are inconsistent.
+pub fn crt<T: Signed>(congruences: impl IntoIterator<Item = (T, T)>) -> Option<(T, T)> {
+    let mut x = T::ZERO;
+    let mut modulus = T::ONE;
+    for (a, m) in congruences {
+        let (g, p, _) = egcd(modulus, m);
+        let diff = a - x;
+        if diff % g != T::ZERO {
+            return None;
+        }
+        let step = m / g;
+        let k = ((diff / g).rem_euclid(step) * p.rem_euclid(step)).rem_euclid(step);
+        x += modulus * k;
+        modulus *= step;
+        x = x.rem_euclid(modulus);
+    }
+    Some((x, modulus))
+}
+
+/// Solves `a · x = b` exactly over the integers.
+///
+/// Returns `None` if the matrix is singular or the unique solution is
+/// not integral.
+pub fn solve_linear<T: Signed, const N: usize>(
+    mut a: [[T; N]; N],
+    mut b: [T; N],
+) -> Option<[T; N]> {
+    // Fraction-free Gauss-Jordan elimination, dividing each row by the
+    // gcd of its entries to keep the values small.
+    for col in 0..N {
+        let pivot = (col..N).find(|&row| a[row][col] != T::ZERO)?;
+        a.swap(col, pivot);
+        b.swap(col, pivot);
+        for row in 0..N {
+            if row == col || a[row][col] == T::ZERO {
+                continue;
+            }
+            let g = gcd(a[col][col], a[row][col]);
+            let (p, q) = (a[col][col] / g, a[row][col] / g);
+            let pivot_row = a[col];
+            for (v, &w) in a[row].iter_mut().zip(&pivot_row) {
+                *v = *v * p - w * q;
+            }
+            b[row] = b[row] * p - b[col] * q;
+
+            let g = a[row].iter().fold(b[row], |g, &v| gcd(g, v));
+            if g > T::ONE {
+                a[row].iter_mut().for_each(|v| *v /= g);
+                b[row] /= g;
+            }
+        }
+    }
+
+    let mut x = [T::ZERO; N];
+    for i in 0..N {
+        if b[i] % a[i][i] != T::ZERO {
+            return None;
+        }
+        x[i] = b[i] / a[i][i];
+    }
+    Some(x)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn number_theory() {
+        assert_eq!(gcd(12u32, 18), 6);
+        assert_eq!(gcd(4i64, -6), 2);
+        assert_eq!(lcm(101i64, 103), 10403);
+        assert_eq!(lcm(-4i32, 6), 12);
+
+        let (g, x, y) = egcd(240i64, 46);
+        assert_eq!((g, 240 * x + 46 * y), (2, 2));
+        assert_eq!(mod_inv(3i64, 11), Some(4));
+        assert_eq!(mod_inv(-3i64, 11), Some(7));
+        assert_eq!(mod_inv(4i64, 8), None);
+        assert_eq!(mod_pow(4u64, 13, 497), 445);
+        assert_eq!(mod_pow(7i128, 1 << 40, 16777216), 1);
+
+        assert_eq!(crt([(2i64, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        assert_eq!(crt([(1i64, 4), (3, 6)]), Some((9, 12)));
+        assert_eq!(crt([(1i64, 4), (2, 6)]), None);
+    }
+
+    #[test]
+    fn linear_systems() {
+        // Claw machines from day 13.
+        assert_eq!(
+            solve_linear([[94i64, 22], [34, 67]], [8400, 5400]),
+            Some([80, 40])
+        );
+        assert_eq!(solve_linear([[26i64, 67], [66, 21]], [12748, 12176]), None);
+        assert_eq!(solve_linear([[1i64, 2], [2, 4]], [3, 6]), None);
+
+        let a = [[2i64, 1, -1], [-3, -1, 2], [-2, 1, 2]];
+        assert_eq!(solve_linear(a, [8, -11, -3]), Some([2, 3, -1]));
+        assert_eq!(solve_linear([[0i32, 1], [1, 0]], [5, 7]), Some([7, 5]));
+    }
+}
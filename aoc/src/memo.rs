@@ -0,0 +1,147 @@
+//! Memoisation for recursive solutions.
+//!
+//! A [`Memo`] caches the results of a recursive function, passing itself
+//! back into the computation so that recursive calls share the cache.
+//! Inside `rayon` iterators a [`PerThread`] gives each worker thread its
+//! own cache without contention.
+
+use std::borrow::Borrow;
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::sync::Mutex;
+
+/// A cache of computed values.
+#[derive(Clone, Debug)]
+pub struct Memo<K, V> {
+    cache: HashMap<K, V>,
+}
+
+impl<K, V> Default for Memo<K, V> {
+    fn default() -> Self {
+        Self {
+            cache: HashMap::new(),
+        }
+    }
+}
+
+impl<K: Hash + Eq, V: Clone> Memo<K, V> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the cached value for `key`, or computes and caches it.
+    ///
+    /// `key` may be a borrowed form of `K`, such as `&str` for a
+    /// `String` key; it is only converted to an owned key when a new
+    /// value is inserted. `compute` is given the memo so it can recurse.
+    pub fn get_or_compute<Q>(&mut self, key: &Q, compute: impl FnOnce(&mut Self) -> V) -> V
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ToOwned<Owned = K> + ?Sized,
+    {
+        if let Some(value) = self.cache.get(key) {
+            return value.clone();
+        }
+        let value = compute(self);
+        self.cache.insert(key.to_owned(), value.clone());
+        value
+    }
+
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.cache.get(key)
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.cache.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.cache.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.cache.clear();
+    }
+}
+
+/// A value with a separate instance for each `rayon` worker thread.
+///
+/// Threads outside the pool share an extra instance. Each instance is
+/// behind a mutex, so the closure passed to [`PerThread::with`] must not
+/// call back into `rayon`, or work stealing could re-enter the same
+/// instance and deadlock.
+#[derive(Debug)]
+pub struct PerThread<T> {
+    slots: Vec<Mutex<T>>,
+}
+
+impl<T: Default> Default for PerThread<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T: Default> PerThread<T> {
+    pub fn new() -> Self {
+        let slots = (0..=rayon::current_num_threads())
+            .map(|_| Mutex::default())
+            .collect();
+        Self { slots }
+    }
+}
+
+impl<T> PerThread<T> {
+    /// Runs `f` with the current thread's instance.
+    pub fn with<R>(&self, f: impl FnOnce(&mut T) -> R) -> R {
+        let index = rayon::current_thread_index().map_or(0, |i| i + 1);
+        let slot = self.slots.get(index).unwrap_or(&self.slots[0]);
+        let mut value = slot.lock().unwrap_or_else(|err| err.into_inner());
+        f(&mut value)
+    }
+
+    /// Consumes the per-thread instances.
+    pub fn into_inner(self) -> impl Iterator<Item = T> {
+        self.slots
+            .into_iter()
+            .map(|slot| slot.into_inner().unwrap_or_else(|err| err.into_inner()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rayon::prelude::*;
+
+    fn fib(memo: &mut Memo<u64, u64>, n: u64) -> u64 {
+        match n {
+            0 | 1 => n,
+            _ => memo.get_or_compute(&n, |memo| fib(memo, n - 1) + fib(memo, n - 2)),
+        }
+    }
+
+    #[test]
+    fn memoise() {
+        let mut memo = Memo::new();
+        assert_eq!(fib(&mut memo, 90), 2880067194370816120);
+        assert_eq!(memo.len(), 89);
+
+        let mut lengths: Memo<String, usize> = Memo::new();
+        assert_eq!(lengths.get_or_compute("abc", |_| 3), 3);
+        assert_eq!(lengths.get_or_compute("abc", |_| unreachable!()), 3);
+        assert_eq!(lengths.get("abc"), Some(&3));
+
+        let memos: PerThread<Memo<u64, u64>> = PerThread::new();
+        let total: u64 = (0..50u64)
+            .into_par_iter()
+            .map(|n| memos.with(|memo| fib(memo, n)))
+            .sum();
+        assert_eq!(total, fib(&mut memo, 51) - 1);
+        assert!(memos.into_inner().any(|memo| memo.get(&48).is_some()));
+    }
+}
@@ -0,0 +1,85 @@
+use std::fmt::{Debug, Display};
+use std::hash::Hash;
+use std::ops;
+
+/// Primitive integer types.
+pub trait Int:
+    Copy
+    + Default
+    + Eq
+    + Ord
+    + Hash
+    + Debug
+    + Display
+    + ops::Add<Output = Self>
+    + ops::Sub<Output = Self>
+    + ops::Mul<Output = Self>
+    + ops::Div<Output = Self>
+    + ops::Rem<Output = Self>
+    + ops::AddAssign
+    + ops::SubAssign
+    + ops::MulAssign
+    + ops::DivAssign
+    + ops::RemAssign
+{
+    const ZERO: Self;
+    const ONE: Self;
+
+    fn rem_euclid(self, rhs: Self) -> Self;
+
+    /// Returns `|self - other|` without underflowing unsigned types.
+    #[inline]
+    fn abs_diff(self, other: Self) -> Self {
+        if self > other {
+            self - other
+        } else {
+            other - self
+        }
+    }
+}
+
+/// Signed primitive integer types.
+pub trait Signed: Int + ops::Neg<Output = Self> {
+    fn abs(self) -> Self;
+    fn signum(self) -> Self;
+}
+
+macro_rules! impl_int {
+    ($($t:ty),+) => {$(
+        impl Int for $t {
+            const ZERO: Self = 0;
+            const ONE: Self = 1;
+
+            #[inline]
+            fn rem_euclid(self, rhs: Self) -> Self {
+                <$t>::rem_euclid(self, rhs)
+            }
+        }
+    )+};
+}
+
+macro_rules! impl_signed {
+    ($($t:ty),+) => {$(
+        impl Signed for $t {
+            #[inline]
+            fn abs(self) -> Self {
+                <$t>::abs(self)
+            }
+
+            #[inline]
+            fn signum(self) -> Self {
+                <$t>::signum(self)
+            }
+        }
+    )+};
+}
+
+impl_int!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+impl_signed!(i8, i16, i32, i64, i128, isize);
+
+/// Iterates over `start..=end` for any [`Int`].
+pub(crate) fn range_inclusive<T: Int>(start: T, end: T) -> impl Iterator<Item = T> {
+    std::iter::successors((start <= end).then_some(start), move |&v| {
+        (v < end).then(|| v + T::ONE)
+    })
+}
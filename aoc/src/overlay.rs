@@ -0,0 +1,116 @@
+use crate::{Grid, Vec2};
+
+/// A copy-on-write view of a [`Grid`] that records changes to a small
+/// number of cells without cloning the underlying grid.
+///
+/// Changed cells are kept in a list which is scanned on every lookup,
+/// so an overlay is intended for a handful of changes (eg. placing a
+/// single obstacle per worker thread).
+#[derive(Clone, Debug)]
+pub struct Overlay<'g, T> {
+    base: &'g Grid<T>,
+    changes: Vec<(Vec2, T)>,
+}
+
+impl<'g, T> Overlay<'g, T> {
+    /// Creates a new `Overlay` with no changes.
+    pub fn new(base: &'g Grid<T>) -> Self {
+        Self {
+            base,
+            changes: vec![],
+        }
+    }
+
+    /// Returns the grid underneath the overlay.
+    #[inline]
+    pub fn base(&self) -> &'g Grid<T> {
+        self.base
+    }
+
+    #[inline]
+    pub fn cols(&self) -> usize {
+        self.base.cols()
+    }
+
+    #[inline]
+    pub fn rows(&self) -> usize {
+        self.base.rows()
+    }
+
+    #[inline]
+    pub fn in_bounds(&self, pos: &Vec2) -> bool {
+        self.base.in_bounds(pos)
+    }
+
+    #[inline]
+    pub fn get(&self, pos: &Vec2) -> Option<&T> {
+        match self.changes.iter().find(|(p, _)| p == pos) {
+            Some((_, value)) => Some(value),
+            None => self.base.get(pos),
+        }
+    }
+
+    /// Sets the value of a cell in the overlay, leaving the underlying
+    /// grid untouched.
+    pub fn set(&mut self, pos: impl Into<Vec2>, value: T) {
+        let pos = pos.into();
+        assert!(self.in_bounds(&pos));
+        match self.changes.iter_mut().find(|(p, _)| *p == pos) {
+            Some((_, old)) => *old = value,
+            None => self.changes.push((pos, value)),
+        }
+    }
+
+    /// Discards all changes made to the overlay.
+    pub fn reset(&mut self) {
+        self.changes.clear();
+    }
+}
+
+impl<T: Clone> Overlay<'_, T> {
+    /// Copies the underlying grid with the changes applied.
+    pub fn to_grid(&self) -> Grid<T> {
+        let mut grid = self.base.clone();
+        for (pos, value) in &self.changes {
+            grid[*pos] = value.clone();
+        }
+        grid
+    }
+}
+
+impl<T> std::ops::Index<Vec2> for Overlay<'_, T> {
+    type Output = T;
+
+    #[inline]
+    fn index(&self, pos: Vec2) -> &Self::Output {
+        match self.changes.iter().find(|(p, _)| *p == pos) {
+            Some((_, value)) => value,
+            None => &self.base[pos],
+        }
+    }
+}
+
+impl<T> Grid<T> {
+    /// Returns a copy-on-write [`Overlay`] of the grid.
+    pub fn overlay(&self) -> Overlay<'_, T> {
+        Overlay::new(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{gridify_ascii, Vec2};
+
+    #[test]
+    fn overlay() {
+        let grid = gridify_ascii("...\n...".lines());
+        let mut overlay = grid.overlay();
+        overlay.set((1, 1), b'#');
+        overlay.set((1, 1), b'O');
+        assert_eq!(overlay[Vec2::new(1, 1)], b'O');
+        assert_eq!(overlay.get(&Vec2::new(0, 1)), Some(&b'.'));
+        assert_eq!(overlay.get(&Vec2::new(3, 1)), None);
+        assert_eq!(overlay.to_grid().to_string(), "...\n.O.\n");
+        assert_eq!(grid[(1, 1)], b'.');
+    }
+}
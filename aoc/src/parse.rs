@@ -0,0 +1,213 @@
+use std::fmt;
+
+/// An error produced while parsing puzzle input, with the location of
+/// the offending text.
+///
+/// Lines and columns are 1-based.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub text: String,
+    pub message: String,
+}
+
+impl ParseError {
+    pub fn new(
+        line: usize,
+        column: usize,
+        text: impl Into<String>,
+        message: impl ToString,
+    ) -> Self {
+        Self {
+            line,
+            column,
+            text: text.into(),
+            message: message.to_string(),
+        }
+    }
+
+    /// Moves the error to the specified line.
+    #[must_use]
+    pub fn at_line(mut self, line: usize) -> Self {
+        self.line = line;
+        self
+    }
+
+    /// Shifts the error right by `offset` columns.
+    #[must_use]
+    pub fn offset_column(mut self, offset: usize) -> Self {
+        self.column += offset;
+        self
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: {} (in {:?})",
+            self.line, self.column, self.message, self.text
+        )
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Parses a value, returning a [`ParseError`] located at line 1,
+/// column 1 on failure.
+pub fn try_parse<T>(s: impl AsRef<str>) -> Result<T, ParseError>
+where
+    T: std::str::FromStr,
+    T::Err: fmt::Display,
+{
+    let s = s.as_ref();
+    s.parse().map_err(|err| ParseError::new(1, 1, s, err))
+}
+
+/// Parses `token`, which must be a substring of `line`, reporting the
+/// column of the token on failure.
+#[doc(hidden)]
+pub fn try_parse_token<T>(line: &str, token: &str) -> Result<T, ParseError>
+where
+    T: std::str::FromStr,
+    T::Err: fmt::Display,
+{
+    try_parse(token).map_err(|err| err.offset_column(column_of(line, token)))
+}
+
+/// Parses exactly two values from `parts`, which must be substrings of
+/// `line`.
+#[doc(hidden)]
+pub fn try_parse_pair<'a, A, B>(
+    line: &'a str,
+    mut parts: impl Iterator<Item = &'a str>,
+) -> Result<(A, B), ParseError>
+where
+    A: std::str::FromStr,
+    A::Err: fmt::Display,
+    B: std::str::FromStr,
+    B::Err: fmt::Display,
+{
+    let missing = || ParseError::new(1, line.len() + 1, line, "expected a pair");
+    let a = try_parse_token(line, parts.next().ok_or_else(missing)?)?;
+    let b = try_parse_token(line, parts.next().ok_or_else(missing)?)?;
+    match parts.next() {
+        None => Ok((a, b)),
+        Some(extra) => {
+            let message = "expected a pair, found an extra element";
+            Err(ParseError::new(1, 1, extra, message).offset_column(column_of(line, extra)))
+        }
+    }
+}
+
+/// Returns the 0-based byte offset of `token` within `line`, or 0 if it
+/// is not a substring of `line`.
+fn column_of(line: &str, token: &str) -> usize {
+    let offset = (token.as_ptr() as usize).wrapping_sub(line.as_ptr() as usize);
+    if offset <= line.len() {
+        offset
+    } else {
+        0
+    }
+}
+
+/// Returns an iterator over the integers in `s`, ignoring any other text.
+///
+/// A `-` immediately before the digits is treated as a sign unless it
+/// follows a letter or digit, so `x=-3` yields -3 while `1-3` yields 1
+/// and 3. Panics if an integer does not fit in `T`; [`ints_n`] returns
+/// an error instead.
+pub fn ints<T>(s: &str) -> impl Iterator<Item = T> + '_
+where
+    T: std::str::FromStr,
+    T::Err: fmt::Display,
+{
+    int_tokens(s, true).map(|token| try_parse(token).unwrap_or_else(|err| panic!("{err}")))
+}
+
+/// Like [`ints`], but ignores signs.
+pub fn uints<T>(s: &str) -> impl Iterator<Item = T> + '_
+where
+    T: std::str::FromStr,
+    T::Err: fmt::Display,
+{
+    int_tokens(s, false).map(|token| try_parse(token).unwrap_or_else(|err| panic!("{err}")))
+}
+
+/// Returns exactly `N` integers from `s`, as found by [`ints`].
+pub fn ints_n<T, const N: usize>(s: &str) -> Result<[T; N], ParseError>
+where
+    T: std::str::FromStr,
+    T::Err: fmt::Display,
+{
+    let mut tokens = int_tokens(s, true);
+    let mut values = [(); N].map(|_| None);
+    for (found, value) in values.iter_mut().enumerate() {
+        let Some(token) = tokens.next() else {
+            let message = format!("expected {N} integers, found {found}");
+            return Err(locate(s, &s[s.len()..], message));
+        };
+        *value = Some(try_parse(token).map_err(|err| locate(s, token, err.message))?);
+    }
+    if let Some(extra) = tokens.next() {
+        let message = format!("expected {N} integers, found more");
+        return Err(locate(s, extra, message));
+    }
+    Ok(values.map(Option::unwrap))
+}
+
+/// Creates an error for `token`, which must be a substring of `input`,
+/// located at its line and column within `input`.
+pub(crate) fn locate(input: &str, token: &str, message: impl ToString) -> ParseError {
+    let offset = column_of(input, token);
+    let line = input[..offset].matches('\n').count() + 1;
+    let column = offset - input[..offset].rfind('\n').map_or(0, |i| i + 1) + 1;
+    ParseError::new(line, column, token, message)
+}
+
+fn int_tokens(s: &str, signed: bool) -> impl Iterator<Item = &str> {
+    let bytes = s.as_bytes();
+    let mut i = 0;
+    std::iter::from_fn(move || {
+        while i < bytes.len() && !bytes[i].is_ascii_digit() {
+            i += 1;
+        }
+        if i == bytes.len() {
+            return None;
+        }
+        let mut start = i;
+        if signed
+            && start > 0
+            && bytes[start - 1] == b'-'
+            && (start < 2 || !bytes[start - 2].is_ascii_alphanumeric())
+        {
+            start -= 1;
+        }
+        while i < bytes.len() && bytes[i].is_ascii_digit() {
+            i += 1;
+        }
+        Some(&s[start..i])
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn integers() {
+        let values: Vec<i64> = ints("p=0,4 v=3,-3 x-5 1-3 --7").collect();
+        assert_eq!(values, [0, 4, 3, -3, 5, 1, 3, -7]);
+        let values: Vec<u8> = uints("Button A: X+94, Y-34").collect();
+        assert_eq!(values, [94, 34]);
+
+        assert_eq!(ints_n::<i32, 2>("X+94, Y=-34"), Ok([94, -34]));
+        let err = ints_n::<u8, 2>("Register A: 7").unwrap_err();
+        assert_eq!((err.line, err.column), (1, 14));
+        let err = ints_n::<u8, 2>("1\n2\nA: 3").unwrap_err();
+        assert_eq!((err.line, err.column, err.text.as_str()), (3, 4, "3"));
+        let err = ints_n::<u8, 1>("a\nb=300").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 3));
+    }
+}
@@ -0,0 +1,269 @@
+//! Sets of integers stored as sorted, disjoint half-open ranges.
+//!
+//! Useful for merging intervals, tracking free space and clipping
+//! ranges against each other without materialising every value.
+
+use crate::Int;
+use std::collections::{BTreeMap, BTreeSet};
+use std::ops::Range;
+
+/// A set of integers stored as the minimal set of disjoint, non-adjacent,
+/// non-empty half-open ranges.
+///
+/// The ranges are kept in a map ordered by start, along with an index of
+/// their starts by length, so that inserting, removing and finding the
+/// first range that fits all take logarithmic time.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RangeSet<T> {
+    /// The end of each range, keyed by its start.
+    ranges: BTreeMap<T, T>,
+    /// The starts of the ranges, grouped by length.
+    by_len: BTreeMap<T, BTreeSet<T>>,
+}
+
+impl<T> Default for RangeSet<T> {
+    fn default() -> Self {
+        Self {
+            ranges: BTreeMap::new(),
+            by_len: BTreeMap::new(),
+        }
+    }
+}
+
+impl<T: Int> RangeSet<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Iterates over the ranges in ascending order.
+    #[inline]
+    pub fn ranges(&self) -> impl DoubleEndedIterator<Item = Range<T>> + '_ {
+        self.ranges.iter().map(|(&start, &end)| start..end)
+    }
+
+    /// Returns the number of disjoint ranges.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.ranges.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// Returns the number of values in the set.
+    pub fn total_len(&self) -> T {
+        self.ranges()
+            .fold(T::ZERO, |total, r| total + (r.end - r.start))
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        self.ranges
+            .range(..=value)
+            .next_back()
+            .is_some_and(|(_, &end)| value < end)
+    }
+
+    /// Adds every value in `range`, merging it with any ranges it
+    /// overlaps or touches.
+    pub fn insert(&mut self, range: Range<T>) {
+        if range.is_empty() {
+            return;
+        }
+        let (mut start, mut end) = (range.start, range.end);
+        if let Some((&s, &e)) = self.ranges.range(..start).next_back() {
+            if e >= start {
+                self.take(s);
+                start = s;
+                end = end.max(e);
+            }
+        }
+        while let Some((&s, &e)) = self.ranges.range(start..=end).next() {
+            self.take(s);
+            end = end.max(e);
+        }
+        self.put(start..end);
+    }
+
+    /// Removes every value in `range`, splitting any range it falls
+    /// inside.
+    pub fn remove(&mut self, range: Range<T>) {
+        if range.is_empty() {
+            return;
+        }
+        if let Some((&s, &e)) = self.ranges.range(..range.start).next_back() {
+            if e > range.start {
+                self.take(s);
+                self.put(s..range.start);
+                self.put(range.end..e);
+            }
+        }
+        while let Some((&s, &e)) = self.ranges.range(range.clone()).next() {
+            self.take(s);
+            self.put(range.end..e);
+        }
+    }
+
+    /// Adds every value in `other` to the set.
+    pub fn merge(&mut self, other: &RangeSet<T>) {
+        self.extend(other.ranges());
+    }
+
+    /// Returns the values in both `self` and `other`.
+    pub fn intersect(&self, other: &RangeSet<T>) -> RangeSet<T> {
+        let mut intersection = Self::new();
+        let (mut a, mut b) = (self.ranges().peekable(), other.ranges().peekable());
+        while let (Some(x), Some(y)) = (a.peek(), b.peek()) {
+            intersection.put(x.start.max(y.start)..x.end.min(y.end));
+            match x.end < y.end {
+                true => a.next(),
+                false => b.next(),
+            };
+        }
+        intersection
+    }
+
+    /// Returns the values in `within` which are not in the set.
+    pub fn complement(&self, within: Range<T>) -> RangeSet<T> {
+        let mut complement = Self::from_iter([within]);
+        for range in self.ranges() {
+            complement.remove(range);
+        }
+        complement
+    }
+
+    /// Returns the start of the lowest range holding at least `n`
+    /// consecutive values.
+    ///
+    /// Only the lowest range of each length is considered, so this takes
+    /// time proportional to the number of distinct lengths of at least
+    /// `n`, not the number of ranges.
+    pub fn first_fit(&self, n: T) -> Option<T> {
+        self.by_len
+            .range(n..)
+            .filter_map(|(_, starts)| starts.first())
+            .min()
+            .copied()
+    }
+
+    /// Removes the lowest `n` consecutive values that fit in a single
+    /// range, returning the start of the allocated span.
+    pub fn allocate(&mut self, n: T) -> Option<T> {
+        let start = self.first_fit(n)?;
+        self.remove(start..start + n);
+        Some(start)
+    }
+
+    /// Adds a range known not to overlap or touch any other, unless it is
+    /// empty.
+    fn put(&mut self, range: Range<T>) {
+        if range.is_empty() {
+            return;
+        }
+        self.ranges.insert(range.start, range.end);
+        self.by_len
+            .entry(range.end - range.start)
+            .or_default()
+            .insert(range.start);
+    }
+
+    /// Removes the range starting at `start`.
+    fn take(&mut self, start: T) {
+        let end = self.ranges.remove(&start).unwrap();
+        let len = end - start;
+        let starts = self.by_len.get_mut(&len).unwrap();
+        starts.remove(&start);
+        if starts.is_empty() {
+            self.by_len.remove(&len);
+        }
+    }
+}
+
+impl<T: Int> FromIterator<Range<T>> for RangeSet<T> {
+    fn from_iter<I: IntoIterator<Item = Range<T>>>(iter: I) -> Self {
+        let mut set = Self::new();
+        set.extend(iter);
+        set
+    }
+}
+
+impl<T: Int> Extend<Range<T>> for RangeSet<T> {
+    fn extend<I: IntoIterator<Item = Range<T>>>(&mut self, iter: I) {
+        for range in iter {
+            self.insert(range);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ranges<T: Int>(set: &RangeSet<T>) -> Vec<Range<T>> {
+        set.ranges().collect()
+    }
+
+    #[test]
+    fn range_set() {
+        let mut set: RangeSet<i64> = [10..15, 1..3, 14..20, 3..4, 30..30].into_iter().collect();
+        assert_eq!(ranges(&set), [1..4, 10..20]);
+        assert_eq!(set.total_len(), 13);
+        assert!(set.contains(3) && !set.contains(4) && set.contains(19) && !set.contains(20));
+
+        set.remove(12..14);
+        set.remove(0..2);
+        assert_eq!(ranges(&set), [2..4, 10..12, 14..20]);
+        set.insert(-5..-3);
+        set.merge(&[3..10, 25..26].into_iter().collect());
+        assert_eq!(ranges(&set), [-5..-3, 2..12, 14..20, 25..26]);
+
+        let other: RangeSet<i64> = [0..3, 11..16, 19..30].into_iter().collect();
+        assert_eq!(
+            ranges(&set.intersect(&other)),
+            [2..3, 11..12, 14..16, 19..20, 25..26]
+        );
+        assert_eq!(
+            ranges(&set.complement(0..30)),
+            [0..2, 12..14, 20..25, 26..30]
+        );
+        assert_eq!(set.first_fit(3), Some(2));
+        assert_eq!(set.first_fit(11), None);
+    }
+
+    #[test]
+    fn disk_compaction() {
+        // Day 9, part 2: move each file, highest id first, to the first
+        // free span to its left that fits.
+        let disk = "2333133121414131402";
+        let mut files = vec![];
+        let mut free = RangeSet::new();
+        let mut location = 0;
+        for (i, length) in disk.bytes().map(|b| (b - b'0') as usize).enumerate() {
+            match i % 2 {
+                0 => files.push(location..location + length),
+                _ => free.insert(location..location + length),
+            }
+            location += length;
+        }
+        assert_eq!(free.total_len(), 14);
+
+        for file in files.iter_mut().rev() {
+            let length = file.end - file.start;
+            if let Some(start) = free.first_fit(length).filter(|&s| s < file.start) {
+                free.remove(start..start + length);
+                free.insert(file.clone());
+                *file = start..start + length;
+            }
+        }
+
+        let checksum: usize = (0..)
+            .zip(&files)
+            .map(|(id, file)| id * file.clone().sum::<usize>())
+            .sum();
+        assert_eq!(checksum, 2858);
+        assert_eq!(free.complement(0..location).total_len(), location - 14);
+        assert_eq!(free.allocate(3), Some(18));
+        assert_eq!(free.first_fit(3), Some(31));
+    }
+}
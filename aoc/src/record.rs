@@ -0,0 +1,89 @@
+use std::fs::{File, OpenOptions};
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::Path;
+use std::time::Duration;
+
+/// Returns a stable 64-bit FNV-1a hash of `input`, used to tell puzzle
+/// inputs apart.
+pub fn fingerprint(input: &str) -> u64 {
+    input.bytes().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ byte as u64).wrapping_mul(0x0000_0100_0000_01b3)
+    })
+}
+
+/// Appends an answer and its timing to the tab-separated log at `path`.
+///
+/// Returns the previously recorded answer for the same day, part and
+/// input fingerprint if it differs from `answer`.
+pub fn record(
+    path: impl AsRef<Path>,
+    day: &str,
+    fingerprint: u64,
+    part: &str,
+    answer: &str,
+    elapsed: Duration,
+) -> io::Result<Option<String>> {
+    let path = path.as_ref();
+    let key = format!("{day}\t{fingerprint:016x}\t{part}\t");
+    let answer = answer.escape_default().to_string();
+
+    let mut previous = None;
+    match File::open(path) {
+        Ok(file) => {
+            for line in BufReader::new(file).lines() {
+                let line = line?;
+                if let Some(rest) = line.strip_prefix(&key) {
+                    previous = rest.split('\t').next().map(str::to_string);
+                }
+            }
+        }
+        Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+        Err(err) => return Err(err),
+    }
+
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{key}{answer}\t{}", elapsed.as_nanos())?;
+    Ok(previous.filter(|previous| *previous != answer))
+}
+
+/// Records an answer with [`record`] if the `AOC_RESULTS` environment
+/// variable names a log file, warning if the answer has changed.
+pub fn record_answer(day: &str, fingerprint: u64, part: &str, answer: &str, elapsed: Duration) {
+    let Some(path) = std::env::var_os("AOC_RESULTS") else {
+        return;
+    };
+    match record(&path, day, fingerprint, part, answer, elapsed) {
+        Ok(Some(previous)) => {
+            eprintln!("WARNING: {part} answer changed from {previous} for this input")
+        }
+        Ok(None) => {}
+        Err(err) => eprintln!("WARNING: could not record {part} in {path:?}: {err}"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn record_answers() {
+        assert_eq!(fingerprint(""), 0xcbf2_9ce4_8422_2325);
+        assert_ne!(fingerprint("1 2"), fingerprint("2 1"));
+
+        let path = std::env::temp_dir().join(format!("aoc-record-{}.tsv", std::process::id()));
+        let elapsed = Duration::from_millis(3);
+        let record = |part, answer| record(&path, "day01", 42, part, answer, elapsed).unwrap();
+        assert_eq!(record("part1", "11"), None);
+        assert_eq!(record("part2", "31"), None);
+        assert_eq!(record("part1", "11"), None);
+        assert_eq!(record("part1", "12"), Some("11".to_string()));
+
+        let log = std::fs::read_to_string(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(log.lines().count(), 4);
+        assert_eq!(
+            log.lines().next(),
+            Some("day01\t000000000000002a\tpart1\t11\t3000000")
+        );
+    }
+}
@@ -0,0 +1,170 @@
+use crate::Vec2;
+
+/// An axis-aligned rectangle of integer points.
+///
+/// `min` is inclusive and `max` is exclusive, so a rectangle covering
+/// a grid is `Rect::new((0, 0), (cols, rows))`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Rect {
+    pub min: Vec2,
+    pub max: Vec2,
+}
+
+impl Rect {
+    #[inline]
+    pub fn new(min: impl Into<Vec2>, max: impl Into<Vec2>) -> Self {
+        Self {
+            min: min.into(),
+            max: max.into(),
+        }
+    }
+
+    /// Creates a rectangle of the specified size with its top-left
+    /// corner at `origin`.
+    #[inline]
+    pub fn from_size(origin: impl Into<Vec2>, cols: usize, rows: usize) -> Self {
+        let min = origin.into();
+        Self {
+            min,
+            max: min + Vec2::from((cols, rows)),
+        }
+    }
+
+    /// Returns the smallest rectangle containing all of the points, or
+    /// `None` if there are no points.
+    pub fn bounding(points: impl IntoIterator<Item = Vec2>) -> Option<Self> {
+        let mut points = points.into_iter();
+        let first = points.next()?;
+        let (min, max) = points.fold((first, first), |(min, max), p| (min.min(p), max.max(p)));
+        Some(Self {
+            min,
+            max: max + Vec2::new(1, 1),
+        })
+    }
+
+    #[inline]
+    pub fn width(&self) -> i64 {
+        (self.max.x - self.min.x).max(0)
+    }
+
+    #[inline]
+    pub fn height(&self) -> i64 {
+        (self.max.y - self.min.y).max(0)
+    }
+
+    /// Returns the number of points in the rectangle.
+    #[inline]
+    pub fn area(&self) -> i64 {
+        self.width() * self.height()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.area() == 0
+    }
+
+    #[inline]
+    pub fn contains(&self, &Vec2 { x, y }: &Vec2) -> bool {
+        (x >= self.min.x && x < self.max.x) && (y >= self.min.y && y < self.max.y)
+    }
+
+    /// Returns true if `other` lies entirely inside this rectangle.
+    pub fn contains_rect(&self, other: &Rect) -> bool {
+        other.is_empty()
+            || (other.min.x >= self.min.x
+                && other.min.y >= self.min.y
+                && other.max.x <= self.max.x
+                && other.max.y <= self.max.y)
+    }
+
+    /// Returns the overlap of the two rectangles, if any.
+    pub fn intersect(&self, other: &Rect) -> Option<Rect> {
+        let rect = Rect {
+            min: self.min.max(other.min),
+            max: self.max.min(other.max),
+        };
+        (!rect.is_empty()).then_some(rect)
+    }
+
+    /// Returns the smallest rectangle containing both rectangles.
+    pub fn union(&self, other: &Rect) -> Rect {
+        match (self.is_empty(), other.is_empty()) {
+            (true, _) => *other,
+            (_, true) => *self,
+            _ => Rect {
+                min: self.min.min(other.min),
+                max: self.max.max(other.max),
+            },
+        }
+    }
+
+    /// Grows the rectangle by `amount` on every side, or shrinks it if
+    /// `amount` is negative.
+    #[must_use]
+    pub fn expand(&self, amount: i64) -> Rect {
+        let amount = Vec2::new(amount, amount);
+        Rect {
+            min: self.min - amount,
+            max: (self.max + amount).max(self.min - amount),
+        }
+    }
+
+    /// Splits the rectangle into top-left, top-right, bottom-left and
+    /// bottom-right quadrants.
+    ///
+    /// When the width or height is odd the middle column or row is not
+    /// part of any quadrant.
+    pub fn split_quadrants(&self) -> [Rect; 4] {
+        let (w, h) = (self.width(), self.height());
+        let lo = self.min + Vec2::new(w / 2, h / 2);
+        let hi = self.max - Vec2::new(w / 2, h / 2);
+        [
+            Rect::new(self.min, lo),
+            Rect::new((hi.x, self.min.y), (self.max.x, lo.y)),
+            Rect::new((self.min.x, hi.y), (lo.x, self.max.y)),
+            Rect::new(hi, self.max),
+        ]
+    }
+
+    /// Returns an iterator over the points in the rectangle in
+    /// row-major order.
+    pub fn iter(&self) -> impl Iterator<Item = Vec2> {
+        let (min, max) = (self.min, self.max);
+        (min.y..max.y).flat_map(move |y| (min.x..max.x).map(move |x| Vec2::new(x, y)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rect() {
+        let rect = Rect::from_size((0, 0), 11, 7);
+        assert_eq!(rect.area(), 77);
+        assert!(rect.contains(&Vec2::new(10, 6)));
+        assert!(!rect.contains(&Vec2::new(11, 6)));
+
+        let quads = rect.split_quadrants();
+        assert_eq!(quads[0], Rect::new((0, 0), (5, 3)));
+        assert_eq!(quads[3], Rect::new((6, 4), (11, 7)));
+        assert!(quads.iter().all(|q| q.area() == 15));
+        assert!(!quads.iter().any(|q| q.contains(&Vec2::new(5, 1))));
+
+        let other = Rect::new((8, 5), (20, 20));
+        assert_eq!(rect.intersect(&other), Some(Rect::new((8, 5), (11, 7))));
+        assert_eq!(rect.intersect(&Rect::new((11, 0), (12, 1))), None);
+        assert_eq!(rect.union(&other), Rect::new((0, 0), (20, 20)));
+        assert!(rect.union(&other).contains_rect(&other));
+
+        assert_eq!(rect.expand(1), Rect::new((-1, -1), (12, 8)));
+        assert!(rect.expand(-4).is_empty());
+        assert_eq!(
+            Rect::bounding([Vec2::new(3, 1), Vec2::new(1, 4)]),
+            Some(Rect::new((1, 1), (4, 5)))
+        );
+        let points: Vec<_> = Rect::new((1, 1), (3, 3)).iter().collect();
+        assert_eq!(points.len(), 4);
+        assert_eq!(points[1], Vec2::new(2, 1));
+    }
+}
@@ -0,0 +1,110 @@
+use crate::{Cardinal, Grid, Rect, Vec2};
+
+/// Statistics for a single connected region of a [`Grid`], as returned
+/// by [`Grid::components`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Region {
+    /// The first cell of the region in row-major order.
+    pub start: Vec2,
+    /// The number of cells in the region.
+    pub area: usize,
+    /// The number of cell edges bordering a different region or the
+    /// edge of the grid.
+    pub perimeter: usize,
+    /// The number of straight sides of the region's outline, which is
+    /// the same as the number of corners.
+    pub sides: usize,
+    /// The bounding box of the region.
+    pub bounds: Rect,
+}
+
+impl<T> Grid<T> {
+    /// Labels the connected regions of the grid.
+    ///
+    /// Two orthogonally adjacent cells belong to the same region when
+    /// `same` returns true for their values. Returns a grid of region
+    /// labels, which index into the returned list of regions.
+    pub fn components<F>(&self, same: F) -> (Grid<usize>, Vec<Region>)
+    where
+        F: Fn(&T, &T) -> bool,
+    {
+        const UNLABELLED: usize = usize::MAX;
+        let mut labels = Grid::new_with(self.cols(), self.rows(), UNLABELLED);
+        let mut regions = vec![];
+        let mut stack = vec![];
+
+        for start in self.iter_pos() {
+            if labels[start] != UNLABELLED {
+                continue;
+            }
+
+            let label = regions.len();
+            let mut region = Region {
+                start,
+                area: 0,
+                perimeter: 0,
+                sides: 0,
+                bounds: Rect::from_size(start, 1, 1),
+            };
+
+            labels[start] = label;
+            stack.push(start);
+            while let Some(pos) = stack.pop() {
+                region.area += 1;
+                region.bounds = region.bounds.union(&Rect::from_size(pos, 1, 1));
+                for direction in Cardinal::iter() {
+                    let next = pos + Vec2::from(direction);
+                    match self.get(&next) {
+                        Some(value) if same(&self[pos], value) => {
+                            if labels[next] == UNLABELLED {
+                                labels[next] = label;
+                                stack.push(next);
+                            }
+                        }
+                        _ => region.perimeter += 1,
+                    }
+                }
+            }
+            regions.push(region);
+        }
+
+        // Count corners, each of which starts a new side.
+        for pos in labels.iter_pos() {
+            let label = labels[pos];
+            let inside = |offset: Vec2| labels.get(&(pos + offset)) == Some(&label);
+            for a in Cardinal::iter() {
+                let b = a.next_clockwise();
+                let (a, b) = (Vec2::from(a), Vec2::from(b));
+                match (inside(a), inside(b)) {
+                    (false, false) => regions[label].sides += 1,
+                    (true, true) if !inside(a + b) => regions[label].sides += 1,
+                    _ => {}
+                }
+            }
+        }
+
+        (labels, regions)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{gridify_ascii, Rect};
+
+    #[test]
+    fn components() {
+        let grid = gridify_ascii("AAAA\nBBCD\nBBCC\nEEEC".lines());
+        let (labels, regions) = grid.components(|a, b| a == b);
+        assert_eq!(regions.len(), 5);
+        assert_eq!(labels[(1, 2)], labels[(0, 1)]);
+
+        let c = &regions[labels[(2, 1)]];
+        assert_eq!((c.area, c.perimeter, c.sides), (4, 10, 8));
+        assert_eq!(c.bounds, Rect::new((2, 1), (4, 4)));
+
+        let total: usize = regions.iter().map(|r| r.area * r.perimeter).sum();
+        assert_eq!(total, 140);
+        let total: usize = regions.iter().map(|r| r.area * r.sides).sum();
+        assert_eq!(total, 80);
+    }
+}
@@ -0,0 +1,186 @@
+use crate::{Grid, Vec2};
+use std::collections::HashSet;
+use std::fmt;
+use std::io::IsTerminal;
+
+/// ANSI terminal colours.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Colour {
+    Black,
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    White,
+}
+
+impl Colour {
+    /// Returns the ANSI foreground colour code.
+    fn code(self) -> u8 {
+        match self {
+            Self::Black => 30,
+            Self::Red => 31,
+            Self::Green => 32,
+            Self::Yellow => 33,
+            Self::Blue => 34,
+            Self::Magenta => 35,
+            Self::Cyan => 36,
+            Self::White => 37,
+        }
+    }
+}
+
+struct Layer {
+    cells: HashSet<Vec2>,
+    colour: Colour,
+    glyph: Option<char>,
+}
+
+/// Renders a [`Grid`] as text, with optional coloured overlays and axes.
+///
+/// Colours are only emitted when stdout is a terminal and `NO_COLOR`
+/// is not set, unless overridden with [`Render::colour`].
+pub struct Render<'g, T, F> {
+    grid: &'g Grid<T>,
+    glyph: F,
+    layers: Vec<Layer>,
+    axes: bool,
+    colour: Option<bool>,
+}
+
+impl<'g, T, F> Render<'g, T, F>
+where
+    F: Fn(&T) -> char,
+{
+    pub fn new(grid: &'g Grid<T>, glyph: F) -> Self {
+        Self {
+            grid,
+            glyph,
+            layers: vec![],
+            axes: false,
+            colour: None,
+        }
+    }
+
+    /// Highlights the specified cells in the given colour.
+    ///
+    /// Overlays added later take precedence over earlier ones.
+    pub fn overlay(mut self, cells: impl IntoIterator<Item = Vec2>, colour: Colour) -> Self {
+        self.layers.push(Layer {
+            cells: cells.into_iter().collect(),
+            colour,
+            glyph: None,
+        });
+        self
+    }
+
+    /// Draws the specified cells with `glyph` in the given colour.
+    pub fn overlay_with(
+        mut self,
+        cells: impl IntoIterator<Item = Vec2>,
+        colour: Colour,
+        glyph: char,
+    ) -> Self {
+        self.layers.push(Layer {
+            cells: cells.into_iter().collect(),
+            colour,
+            glyph: Some(glyph),
+        });
+        self
+    }
+
+    /// Draws column and row numbers around the grid.
+    pub fn axes(mut self, axes: bool) -> Self {
+        self.axes = axes;
+        self
+    }
+
+    /// Forces colours on or off, regardless of the terminal.
+    pub fn colour(mut self, colour: bool) -> Self {
+        self.colour = Some(colour);
+        self
+    }
+
+    fn use_colour(&self) -> bool {
+        self.colour.unwrap_or_else(|| {
+            std::io::stdout().is_terminal() && std::env::var_os("NO_COLOR").is_none()
+        })
+    }
+}
+
+impl<T, F> fmt::Display for Render<'_, T, F>
+where
+    F: Fn(&T) -> char,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let colour = self.use_colour();
+        let width = self.grid.rows().saturating_sub(1).to_string().len();
+        let digits = self.grid.cols().saturating_sub(1).to_string().len();
+
+        if self.axes {
+            for place in (0..digits as u32).rev() {
+                write!(f, "{:width$} ", "")?;
+                for col in 0..self.grid.cols() {
+                    match col / 10usize.pow(place) {
+                        0 if place > 0 => write!(f, " ")?,
+                        n => write!(f, "{}", n % 10)?,
+                    }
+                }
+                writeln!(f)?;
+            }
+        }
+
+        for y in 0..self.grid.rows() {
+            if self.axes {
+                write!(f, "{y:>width$} ")?;
+            }
+            for x in 0..self.grid.cols() {
+                let pos = Vec2::from((x, y));
+                let glyph = (self.glyph)(&self.grid[pos]);
+                match self.layers.iter().rev().find(|l| l.cells.contains(&pos)) {
+                    Some(layer) if colour => write!(
+                        f,
+                        "\x1b[{}m{}\x1b[0m",
+                        layer.colour.code(),
+                        layer.glyph.unwrap_or(glyph)
+                    )?,
+                    Some(layer) => write!(f, "{}", layer.glyph.unwrap_or(glyph))?,
+                    None => write!(f, "{glyph}")?,
+                }
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+impl<T> Grid<T> {
+    /// Returns a [`Render`] of the grid using `glyph` to draw each cell.
+    pub fn render<F>(&self, glyph: F) -> Render<'_, T, F>
+    where
+        F: Fn(&T) -> char,
+    {
+        Render::new(self, glyph)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{gridify_ascii, Colour, Vec2};
+
+    #[test]
+    fn render_axes_and_colour() {
+        let grid = gridify_ascii("abcdefghijk\nlmnopqrstuv".lines());
+        let render = grid
+            .render(|&v| v.to_ascii_uppercase() as char)
+            .overlay([Vec2::new(0, 1)], Colour::Green)
+            .axes(true)
+            .colour(true);
+        assert_eq!(
+            render.to_string(),
+            "            1\n  01234567890\n0 ABCDEFGHIJK\n1 \x1b[32mL\x1b[0mMNOPQRSTUV\n"
+        );
+    }
+}
@@ -0,0 +1,217 @@
+use crate::ParseError;
+use regex::Regex;
+
+/// A line format such as `"p={},{} v={},{}"`, used by [`scan!`].
+///
+/// Each `{}` captures the text up to the next occurrence of the literal
+/// text that follows it, or the rest of the line if it is the last
+/// placeholder. A placeholder may instead contain a regular expression,
+/// e.g. `{[a-z]+}`, which may contain its own groups, and `{{` and `}}`
+/// match literal braces.
+///
+/// Patterns made only of `{}` placeholders separated by literal text
+/// are matched with plain string searches; the regex engine is only
+/// used for custom placeholders or adjacent placeholders.
+///
+/// [`scan!`]: crate::scan
+#[derive(Clone, Debug)]
+pub struct Pattern {
+    source: String,
+    literals: Vec<String>,
+    /// The compiled regex and the capture group index of each field.
+    regex: Option<(Regex, Vec<usize>)>,
+}
+
+impl Pattern {
+    /// Compiles a pattern, panicking if it is malformed.
+    pub fn new(pattern: &str) -> Self {
+        let mut literals = vec![String::new()];
+        let mut placeholders = vec![];
+        let mut chars = pattern.chars().peekable();
+        while let Some(c) = chars.next() {
+            match c {
+                '{' if chars.peek() == Some(&'{') => {
+                    chars.next();
+                    literals.last_mut().unwrap().push('{');
+                }
+                '}' if chars.peek() == Some(&'}') => {
+                    chars.next();
+                    literals.last_mut().unwrap().push('}');
+                }
+                '{' => {
+                    let mut depth = 1;
+                    let mut placeholder = String::new();
+                    for c in chars.by_ref() {
+                        depth += match c {
+                            '{' => 1,
+                            '}' => -1,
+                            _ => 0,
+                        };
+                        if depth == 0 {
+                            break;
+                        }
+                        placeholder.push(c);
+                    }
+                    assert_eq!(depth, 0, "unclosed placeholder in {pattern:?}");
+                    placeholders.push(placeholder);
+                    literals.push(String::new());
+                }
+                '}' => panic!("unmatched '}}' in {pattern:?}"),
+                _ => literals.last_mut().unwrap().push(c),
+            }
+        }
+
+        let fast = placeholders.iter().all(String::is_empty)
+            && literals
+                .get(1..literals.len() - 1)
+                .unwrap_or_default()
+                .iter()
+                .all(|l| !l.is_empty());
+        let regex = (!fast).then(|| {
+            let mut re = format!("^{}", regex::escape(&literals[0]));
+            let mut groups = vec![];
+            let mut next_group = 1;
+            for (placeholder, literal) in placeholders.iter().zip(&literals[1..]) {
+                // Skip over any groups inside a custom placeholder so
+                // that each field maps to its own outer group.
+                groups.push(next_group);
+                next_group += match placeholder.as_str() {
+                    "" => {
+                        re.push_str("(.*?)");
+                        1
+                    }
+                    custom => {
+                        re.push_str(&format!("({custom})"));
+                        Regex::new(custom).unwrap().captures_len()
+                    }
+                };
+                re.push_str(&regex::escape(literal));
+            }
+            re.push('$');
+            (Regex::new(&re).unwrap(), groups)
+        });
+
+        Self {
+            source: pattern.to_string(),
+            literals,
+            regex,
+        }
+    }
+
+    /// Returns the number of placeholders in the pattern.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.literals.len() - 1
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the text captured by each placeholder.
+    ///
+    /// Panics if `N` is not the number of placeholders.
+    pub fn fields<'a, const N: usize>(&self, line: &'a str) -> Result<[&'a str; N], ParseError> {
+        assert_eq!(N, self.len(), "wrong field count for {:?}", self.source);
+        let mut fields = [""; N];
+        match &self.regex {
+            Some((re, groups)) => {
+                let caps = re.captures(line).ok_or_else(|| {
+                    ParseError::new(1, 1, line, format!("expected {:?}", self.source))
+                })?;
+                for (field, &group) in fields.iter_mut().zip(groups) {
+                    *field = caps.get(group).map_or("", |m| m.as_str());
+                }
+            }
+            None => {
+                let expected = |rest: &str, literal: &str| {
+                    let column = line.len() - rest.len() + 1;
+                    ParseError::new(1, column, rest, format!("expected {literal:?}"))
+                };
+                let mut rest = line
+                    .strip_prefix(self.literals[0].as_str())
+                    .ok_or_else(|| expected(line, &self.literals[0]))?;
+                for (field, literal) in fields.iter_mut().zip(&self.literals[1..]) {
+                    let end = match literal.as_str() {
+                        "" => rest.len(),
+                        literal => rest.find(literal).ok_or_else(|| expected(rest, literal))?,
+                    };
+                    *field = &rest[..end];
+                    rest = &rest[end + literal.len()..];
+                }
+                if !rest.is_empty() {
+                    return Err(expected(rest, "end of line"));
+                }
+            }
+        }
+        Ok(fields)
+    }
+}
+
+/// Extracts values from a line using a [`Pattern`].
+///
+/// With no types the captured `&str` fields are returned as an array;
+/// with types they are parsed into a tuple; with a closure the parsed
+/// values are passed to its body. All forms return a `Result` with a
+/// [`ParseError`] on failure, and the pattern is compiled once per call
+/// site.
+#[macro_export]
+macro_rules! scan {
+    (@pattern $pat:literal) => {{
+        static PATTERN: std::sync::OnceLock<$crate::Pattern> = std::sync::OnceLock::new();
+        PATTERN.get_or_init(|| $crate::Pattern::new($pat))
+    }};
+    ($line:expr, $pat:literal => |$($name:ident : $ty:ty),+ $(,)?| $body:expr) => {
+        $crate::scan!($line, $pat => $($ty),+).map(|($($name,)+)| $body)
+    };
+    ($line:expr, $pat:literal => $($ty:ty),+ $(,)?) => {{
+        const N: usize = [$(stringify!($ty)),+].len();
+        let line: &str = &$line;
+        $crate::scan!(@pattern $pat)
+            .fields::<N>(line)
+            .and_then(|fields| {
+                let mut fields = fields.into_iter();
+                Ok(($($crate::try_parse_token::<$ty>(line, fields.next().unwrap())?,)+))
+            })
+    }};
+    ($line:expr, $pat:literal) => {
+        $crate::scan!(@pattern $pat).fields($line)
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scan_lines() {
+        let robot = scan!("p=0,4 v=3,-3", "p={},{} v={},{}" => i64, i64, i64, i64);
+        assert_eq!(robot, Ok((0, 4, 3, -3)));
+        let [a, op, b, out] = scan!("x00 AND y00 -> z00", "{} {} {} -> {}").unwrap();
+        assert_eq!([a, op, b, out], ["x00", "AND", "y00", "z00"]);
+
+        let sum = scan!("Prize: X=8400, Y=5400", "Prize: X={}, Y={}" => |x: u32, y: u32| x + y);
+        assert_eq!(sum, Ok(13800));
+
+        let err = scan!("p=0,4 w=3,-3", "p={},{} v={},{}" => i64, i64, i64, i64).unwrap_err();
+        assert_eq!((err.column, err.message.as_str()), (5, "expected \" v=\""));
+        let err = scan!("p=0,x v=3,-3", "p={},{} v={},{}" => i64, i64, i64, i64).unwrap_err();
+        assert_eq!((err.column, err.text.as_str()), (5, "x"));
+
+        let pattern = Pattern::new("{[a-z]+}{[0-9]+} {{{}}}");
+        assert!(pattern.regex.is_some());
+        assert_eq!(pattern.fields("abc123 {x}"), Ok(["abc", "123", "x"]));
+        assert!(pattern.fields::<3>("abc {x}").is_err());
+        assert!(Pattern::new("{}: {}").regex.is_none());
+
+        let pattern = Pattern::new("{(ab)+|(c)}-{([0-9])+}");
+        assert_eq!(pattern.fields("abab-123"), Ok(["abab", "123"]));
+        assert_eq!(pattern.fields("c-4"), Ok(["c", "4"]));
+
+        let literal = Pattern::new("no fields");
+        assert!(literal.is_empty());
+        assert_eq!(literal.fields("no fields"), Ok([]));
+        assert!(literal.fields::<0>("no field").is_err());
+    }
+}
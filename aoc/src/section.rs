@@ -0,0 +1,186 @@
+use crate::ParseError;
+use std::borrow::Cow;
+
+/// A block of non-blank lines from puzzle input.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Section<'a> {
+    /// The text of the section with `\n` line endings, without the
+    /// trailing line ending. Only owned if the input used `\r\n`.
+    pub text: Cow<'a, str>,
+    /// The 1-based line number of the first line of the section.
+    pub line: usize,
+    /// The text of the section as it appears in the input.
+    raw: &'a str,
+}
+
+impl<'a> Section<'a> {
+    fn new(raw: &'a str, line: usize) -> Self {
+        let text = match raw.contains('\r') {
+            true => Cow::Owned(raw.replace("\r\n", "\n")),
+            false => Cow::Borrowed(raw),
+        };
+        Self { text, line, raw }
+    }
+
+    /// Returns an iterator over the lines in the section, which borrow
+    /// from the input.
+    #[inline]
+    pub fn lines(&self) -> std::str::Lines<'a> {
+        self.raw.lines()
+    }
+
+    /// Moves an error located relative to the section so that it is
+    /// located relative to the whole input.
+    #[inline]
+    pub fn locate(&self, err: ParseError) -> ParseError {
+        let line = err.line + self.line - 1;
+        err.at_line(line)
+    }
+
+    /// Parses the section with `f`, locating any error relative to the
+    /// whole input.
+    pub fn try_map<'s, T>(
+        &'s self,
+        f: impl FnOnce(&'s str) -> Result<T, ParseError>,
+    ) -> Result<T, ParseError> {
+        f(&self.text).map_err(|err| self.locate(err))
+    }
+
+    /// Parses each line of the section with `f`, locating any error
+    /// relative to the whole input.
+    pub fn try_map_lines<T, C>(
+        &self,
+        mut f: impl FnMut(&'a str) -> Result<T, ParseError>,
+    ) -> Result<C, ParseError>
+    where
+        C: FromIterator<T>,
+    {
+        self.lines()
+            .enumerate()
+            .map(|(i, line)| f(line).map_err(|err| self.locate(err.at_line(i + 1))))
+            .collect()
+    }
+
+    /// Parses the whole section as a `T`.
+    pub fn parse<T>(&self) -> Result<T, ParseError>
+    where
+        T: std::str::FromStr,
+        T::Err: std::fmt::Display,
+    {
+        self.try_map(crate::try_parse)
+    }
+}
+
+impl std::ops::Deref for Section<'_> {
+    type Target = str;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.text
+    }
+}
+
+/// An iterator over the blank-line separated sections of puzzle input.
+///
+/// Lines containing only whitespace count as blank and runs of blank
+/// lines are treated as a single separator. Line endings are normalised
+/// to `\n` in [`Section::text`].
+#[derive(Clone, Debug)]
+pub struct Sections<'a> {
+    input: &'a str,
+    offset: usize,
+    line: usize,
+}
+
+impl<'a> Sections<'a> {
+    /// Returns exactly `N` sections, or an error if the input contains
+    /// a different number.
+    pub fn exactly<const N: usize>(mut self) -> Result<[Section<'a>; N], ParseError> {
+        let mut sections = Vec::with_capacity(N);
+        for found in 0..N {
+            sections.push(self.next().ok_or_else(|| {
+                let message = format!("expected {N} sections, found {found}");
+                ParseError::new(self.line + 1, 1, "", message)
+            })?);
+        }
+        match self.next() {
+            None => Ok(sections.try_into().unwrap()),
+            Some(extra) => {
+                let message = format!("expected {N} sections, found more");
+                let text = extra.lines().next().unwrap_or_default();
+                Err(ParseError::new(extra.line, 1, text, message))
+            }
+        }
+    }
+}
+
+impl<'a> Iterator for Sections<'a> {
+    type Item = Section<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut start = None;
+        let mut end = self.offset;
+        while self.offset < self.input.len() {
+            let rest = &self.input[self.offset..];
+            let len = rest.find('\n').map_or(rest.len(), |i| i + 1);
+            let blank = rest[..len].trim().is_empty();
+            if blank && start.is_some() {
+                break;
+            }
+            self.offset += len;
+            self.line += 1;
+            if !blank {
+                start.get_or_insert((self.offset - len, self.line));
+                end = self.offset;
+            }
+        }
+        let (start, line) = start?;
+        let raw = self.input[start..end].trim_end_matches(['\r', '\n']);
+        Some(Section::new(raw, line))
+    }
+}
+
+/// Splits puzzle input into blank-line separated [`Section`]s.
+pub fn sections(input: &str) -> Sections<'_> {
+    Sections {
+        input,
+        offset: 0,
+        line: 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn split_sections() {
+        let input = "\r\na: 1\r\nb: 2\r\n\r\n  \r\n\r\n7\r\n8\r\n";
+        let [first, second] = sections(input).exactly().unwrap();
+        assert_eq!((&*first.text, first.line), ("a: 1\nb: 2", 2));
+        assert_eq!(first.lines().collect::<Vec<_>>(), ["a: 1", "b: 2"]);
+        assert_eq!((&*second.text, second.line), ("7\n8", 7));
+        assert!(matches!(
+            sections("x\ny").next().unwrap().text,
+            Cow::Borrowed("x\ny")
+        ));
+
+        let numbers = second.try_map(|text| text.lines().map(crate::try_parse).collect());
+        assert_eq!(numbers, Ok(vec![7u8, 8]));
+        let err = second.try_map(|_| Err::<(), _>(ParseError::new(2, 1, "8", "oops")));
+        assert_eq!(err.unwrap_err().line, 8);
+        assert_eq!(second.parse::<u8>().unwrap_err().line, 7);
+        let numbers: Result<Vec<u8>, _> = second.try_map_lines(crate::try_parse);
+        assert_eq!(numbers, Ok(vec![7, 8]));
+        let err = first
+            .try_map_lines::<u8, Vec<_>>(crate::try_parse)
+            .unwrap_err();
+        assert_eq!((err.line, err.text.as_str()), (2, "a: 1"));
+
+        let err = sections(input).exactly::<3>().unwrap_err();
+        assert_eq!(err.message, "expected 3 sections, found 2");
+        let err = sections(input).exactly::<1>().unwrap_err();
+        assert_eq!((err.line, err.text.as_str()), (7, "7"));
+        assert_eq!(sections("\n\n").count(), 0);
+    }
+}
@@ -1,146 +1,413 @@
+use crate::math::gcd;
+use crate::num::{range_inclusive, Int, Signed};
 use crate::Cardinal;
-use std::ops;
 
+/// The signed two-dimensional vector used throughout the grid types.
+pub type Vec2 = Vec2T<i64>;
+
+/// A two-dimensional vector, generic over the integer type.
 #[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
-pub struct Vec2 {
-    pub x: i64,
-    pub y: i64,
+pub struct Vec2T<T> {
+    pub x: T,
+    pub y: T,
 }
 
-impl Vec2 {
+impl<T> Vec2T<T> {
     #[inline]
     #[must_use]
-    pub const fn new(x: i64, y: i64) -> Self {
+    pub const fn new(x: T, y: T) -> Self {
         Self { x, y }
     }
 
+    pub fn with_x(mut self, x: T) -> Self {
+        self.x = x;
+        self
+    }
+
+    pub fn with_y(mut self, y: T) -> Self {
+        self.y = y;
+        self
+    }
+}
+
+impl<T: Int> Vec2T<T> {
     #[inline]
     #[must_use]
-    pub fn left() -> Self {
-        Vec2 { x: -1, y: 0 }
+    pub fn right() -> Self {
+        Vec2T {
+            x: T::ONE,
+            y: T::ZERO,
+        }
     }
 
     #[inline]
     #[must_use]
-    pub fn right() -> Self {
-        Vec2 { x: 1, y: 0 }
+    pub fn down() -> Self {
+        Vec2T {
+            x: T::ZERO,
+            y: T::ONE,
+        }
     }
 
+    pub fn translate(self, r: impl Into<Vec2T<T>>) -> Self {
+        self + r.into()
+    }
+
+    /// Component-wise `rem_euclid`.
     #[inline]
     #[must_use]
-    pub fn up() -> Self {
-        Vec2 { x: 0, y: -1 }
+    pub fn rem_euclid(self, rhs: impl Into<Vec2T<T>>) -> Self {
+        let rhs = rhs.into();
+        Vec2T {
+            x: self.x.rem_euclid(rhs.x),
+            y: self.y.rem_euclid(rhs.y),
+        }
     }
 
+    /// Component-wise minimum.
     #[inline]
     #[must_use]
-    pub fn down() -> Self {
-        Vec2 { x: 0, y: 1 }
+    pub fn min(self, rhs: Vec2T<T>) -> Self {
+        Vec2T {
+            x: self.x.min(rhs.x),
+            y: self.y.min(rhs.y),
+        }
     }
 
+    /// Component-wise maximum.
     #[inline]
     #[must_use]
-    pub fn reverse(&self) -> Self {
-        let Vec2 { x, y } = self;
-        Vec2 { x: -x, y: -y }
+    pub fn max(self, rhs: Vec2T<T>) -> Self {
+        Vec2T {
+            x: self.x.max(rhs.x),
+            y: self.y.max(rhs.y),
+        }
     }
 
-    pub fn with_x(mut self, x: i64) -> Self {
-        self.x = x;
-        self
+    /// Component-wise clamp of each coordinate to `min..=max`.
+    #[inline]
+    #[must_use]
+    pub fn clamp(self, min: Vec2T<T>, max: Vec2T<T>) -> Self {
+        Vec2T {
+            x: self.x.clamp(min.x, max.x),
+            y: self.y.clamp(min.y, max.y),
+        }
     }
 
-    pub fn with_y(mut self, y: i64) -> Self {
-        self.y = y;
-        self
+    /// Returns the Manhattan (taxicab) distance to `other`.
+    #[inline]
+    pub fn manhattan(&self, other: &Vec2T<T>) -> T {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y)
     }
 
-    pub fn translate(mut self, r: impl Into<Vec2>) -> Self {
-        let r = r.into();
-        self.x += r.x;
-        self.y += r.y;
-        self
+    /// Returns the Chebyshev (chessboard) distance to `other`.
+    #[inline]
+    pub fn chebyshev(&self, other: &Vec2T<T>) -> T {
+        self.x.abs_diff(other.x).max(self.y.abs_diff(other.y))
     }
-}
 
-impl std::fmt::Display for Vec2 {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "({}, {})", self.x, self.y)
+    /// Returns the square of the Euclidean distance to `other`.
+    #[inline]
+    pub fn euclidean_sq(&self, other: &Vec2T<T>) -> T {
+        let (x, y) = (self.x.abs_diff(other.x), self.y.abs_diff(other.y));
+        x * x + y * y
+    }
+
+    #[inline]
+    pub fn dot(&self, other: &Vec2T<T>) -> T {
+        self.x * other.x + self.y * other.y
     }
 }
 
-impl ops::Sub<Vec2> for Vec2 {
-    type Output = Vec2;
+impl<T: Signed> Vec2T<T> {
     #[inline]
-    fn sub(self, rhs: Self) -> Self::Output {
-        let Vec2 { x: ax, y: ay } = self;
-        let Vec2 { x: bx, y: by } = rhs;
-        Vec2 {
-            x: ax - bx,
-            y: ay - by,
+    #[must_use]
+    pub fn left() -> Self {
+        -Self::right()
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn up() -> Self {
+        -Self::down()
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn reverse(&self) -> Self {
+        let Vec2T { x, y } = *self;
+        Vec2T { x: -x, y: -y }
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn abs(self) -> Self {
+        Vec2T {
+            x: self.x.abs(),
+            y: self.y.abs(),
         }
     }
-}
 
-impl ops::Sub<&Vec2> for &Vec2 {
-    type Output = Vec2;
     #[inline]
-    fn sub(self, rhs: &Vec2) -> Self::Output {
-        let Vec2 { x: ax, y: ay } = self;
-        let Vec2 { x: bx, y: by } = rhs;
-        Vec2 {
-            x: ax - bx,
-            y: ay - by,
+    #[must_use]
+    pub fn signum(self) -> Self {
+        Vec2T {
+            x: self.x.signum(),
+            y: self.y.signum(),
         }
     }
-}
 
-impl ops::Add<Vec2> for Vec2 {
-    type Output = Vec2;
+    /// Rotates the vector 90 degrees clockwise, assuming the y axis
+    /// points down (eg. [`Vec2::up`] becomes [`Vec2::right`]).
     #[inline]
-    fn add(self, rhs: Vec2) -> Self::Output {
-        let Vec2 { x: ax, y: ay } = self;
-        let Vec2 { x: bx, y: by } = rhs;
-        Vec2 {
-            x: ax + bx,
-            y: ay + by,
+    #[must_use]
+    pub fn rotate_cw(self) -> Self {
+        Vec2T {
+            x: -self.y,
+            y: self.x,
         }
     }
-}
 
-impl ops::AddAssign for Vec2 {
+    /// Rotates the vector 90 degrees counter-clockwise, assuming the y
+    /// axis points down (eg. [`Vec2::up`] becomes [`Vec2::left`]).
     #[inline]
-    fn add_assign(&mut self, rhs: Self) {
-        self.x += rhs.x;
-        self.y += rhs.y;
+    #[must_use]
+    pub fn rotate_ccw(self) -> Self {
+        Vec2T {
+            x: self.y,
+            y: -self.x,
+        }
     }
-}
 
-impl<T: Into<i64>> ops::Mul<T> for Vec2 {
-    type Output = Vec2;
+    /// Returns the perpendicular vector `(-y, x)`.
     #[inline]
-    fn mul(self, rhs: T) -> Self::Output {
-        let Vec2 { x, y } = self;
-        let rhs = rhs.into();
-        Vec2 {
-            x: x * rhs,
-            y: y * rhs,
+    #[must_use]
+    pub fn perp(self) -> Self {
+        self.rotate_cw()
+    }
+
+    /// Returns the z component of the cross product of the two vectors
+    /// extended into three dimensions.
+    #[inline]
+    pub fn cross(&self, other: &Vec2T<T>) -> T {
+        self.x * other.y - self.y * other.x
+    }
+
+    /// Returns the smallest integer step in the same direction as the
+    /// vector, by dividing both components by their greatest common
+    /// divisor.
+    #[inline]
+    #[must_use]
+    pub fn reduced(self) -> Self {
+        match gcd(self.x.abs(), self.y.abs()) {
+            d if d == T::ZERO => self,
+            d => Vec2T::new(self.x / d, self.y / d),
         }
     }
+
+    /// Iterates over all points within the specified Manhattan
+    /// distance, including the point itself.
+    pub fn within_manhattan(self, radius: T) -> impl Iterator<Item = Vec2T<T>> {
+        range_inclusive(-radius, radius).flat_map(move |dy| {
+            let width = radius - dy.abs();
+            range_inclusive(-width, width).map(move |dx| self + Vec2T::new(dx, dy))
+        })
+    }
+
+    /// Iterates over all points within the specified Chebyshev
+    /// distance, including the point itself.
+    pub fn within_chebyshev(self, radius: T) -> impl Iterator<Item = Vec2T<T>> {
+        range_inclusive(-radius, radius).flat_map(move |dy| {
+            range_inclusive(-radius, radius).map(move |dx| self + Vec2T::new(dx, dy))
+        })
+    }
+
+    /// Iterates over the integer points on the line from `self` to `end`,
+    /// inclusive of both ends.
+    pub fn line_to(self, end: Vec2T<T>) -> impl Iterator<Item = Vec2T<T>> {
+        let delta = end - self;
+        let step = delta.reduced();
+        let steps = if step.x != T::ZERO {
+            delta.x / step.x
+        } else if step.y != T::ZERO {
+            delta.y / step.y
+        } else {
+            T::ZERO
+        };
+        range_inclusive(T::ZERO, steps).map(move |i| self + Vec2T::new(step.x * i, step.y * i))
+    }
 }
 
-impl<T: Into<i64>> ops::MulAssign<T> for Vec2 {
-    #[inline]
-    fn mul_assign(&mut self, rhs: T) {
-        let rhs = rhs.into();
-        self.x *= rhs;
-        self.y *= rhs;
+impl<T: std::fmt::Display> std::fmt::Display for Vec2T<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
     }
 }
 
+/// Implements the arithmetic operators component-wise for a vector
+/// type, along with scalar multiplication, division and remainder.
+macro_rules! impl_vec_ops {
+    ($V:ident { $($f:ident),+ }) => {
+        $crate::vec2::impl_vec_ops!(@op $V { $($f),+ } Add, add, AddAssign, add_assign, +);
+        $crate::vec2::impl_vec_ops!(@op $V { $($f),+ } Sub, sub, SubAssign, sub_assign, -);
+        $crate::vec2::impl_vec_ops!(@op $V { $($f),+ } Mul, mul, MulAssign, mul_assign, *);
+        $crate::vec2::impl_vec_ops!(@op $V { $($f),+ } Div, div, DivAssign, div_assign, /);
+        $crate::vec2::impl_vec_ops!(@op $V { $($f),+ } Rem, rem, RemAssign, rem_assign, %);
+        $crate::vec2::impl_vec_ops!(@scalar $V { $($f),+ } Mul, mul, MulAssign, mul_assign, *);
+        $crate::vec2::impl_vec_ops!(@scalar $V { $($f),+ } Div, div, DivAssign, div_assign, /);
+        $crate::vec2::impl_vec_ops!(@scalar $V { $($f),+ } Rem, rem, RemAssign, rem_assign, %);
+
+        impl<T: $crate::Signed> std::ops::Neg for $V<T> {
+            type Output = $V<T>;
+            #[inline]
+            fn neg(self) -> Self::Output {
+                $V { $($f: -self.$f),+ }
+            }
+        }
+
+        impl<T: $crate::Signed> std::ops::Neg for &$V<T> {
+            type Output = $V<T>;
+            #[inline]
+            fn neg(self) -> Self::Output {
+                -*self
+            }
+        }
+
+        impl<T: $crate::Int> std::iter::Sum for $V<T> {
+            fn sum<I: Iterator<Item = $V<T>>>(iter: I) -> Self {
+                iter.fold($V::default(), |acc, v| acc + v)
+            }
+        }
+
+        impl<'a, T: $crate::Int> std::iter::Sum<&'a $V<T>> for $V<T> {
+            fn sum<I: Iterator<Item = &'a $V<T>>>(iter: I) -> Self {
+                iter.fold($V::default(), |acc, v| acc + v)
+            }
+        }
+
+        impl<T: Copy> From<&$V<T>> for $V<T> {
+            fn from(value: &$V<T>) -> Self {
+                *value
+            }
+        }
+    };
+    (@op $V:ident { $($f:ident),+ } $tr:ident, $m:ident, $atr:ident, $am:ident, $op:tt) => {
+        impl<T: $crate::Int> std::ops::$tr<$V<T>> for $V<T> {
+            type Output = $V<T>;
+            #[inline]
+            fn $m(self, rhs: $V<T>) -> Self::Output {
+                $V { $($f: self.$f $op rhs.$f),+ }
+            }
+        }
+
+        impl<T: $crate::Int> std::ops::$tr<&$V<T>> for $V<T> {
+            type Output = $V<T>;
+            #[inline]
+            fn $m(self, rhs: &$V<T>) -> Self::Output {
+                self $op *rhs
+            }
+        }
+
+        impl<T: $crate::Int> std::ops::$tr<$V<T>> for &$V<T> {
+            type Output = $V<T>;
+            #[inline]
+            fn $m(self, rhs: $V<T>) -> Self::Output {
+                *self $op rhs
+            }
+        }
+
+        impl<T: $crate::Int> std::ops::$tr<&$V<T>> for &$V<T> {
+            type Output = $V<T>;
+            #[inline]
+            fn $m(self, rhs: &$V<T>) -> Self::Output {
+                *self $op *rhs
+            }
+        }
+
+        impl<T: $crate::Int> std::ops::$atr<$V<T>> for $V<T> {
+            #[inline]
+            fn $am(&mut self, rhs: $V<T>) {
+                *self = *self $op rhs;
+            }
+        }
+
+        impl<T: $crate::Int> std::ops::$atr<&$V<T>> for $V<T> {
+            #[inline]
+            fn $am(&mut self, rhs: &$V<T>) {
+                *self = *self $op *rhs;
+            }
+        }
+    };
+    (@scalar $V:ident { $($f:ident),+ } $tr:ident, $m:ident, $atr:ident, $am:ident, $op:tt) => {
+        // `i64` vectors accept any scalar that widens to `i64`, so that
+        // eg. `pos * steps` works with a `u32` or `i32` count.
+        impl<S: Into<i64>> std::ops::$tr<S> for $V<i64> {
+            type Output = $V<i64>;
+            #[inline]
+            fn $m(self, rhs: S) -> Self::Output {
+                let rhs = rhs.into();
+                $V { $($f: self.$f $op rhs),+ }
+            }
+        }
+
+        impl<S: Into<i64>> std::ops::$tr<S> for &$V<i64> {
+            type Output = $V<i64>;
+            #[inline]
+            fn $m(self, rhs: S) -> Self::Output {
+                *self $op rhs
+            }
+        }
+
+        impl<S: Into<i64>> std::ops::$atr<S> for $V<i64> {
+            #[inline]
+            fn $am(&mut self, rhs: S) {
+                *self = *self $op rhs;
+            }
+        }
+
+        $crate::vec2::impl_vec_ops!(@scalar_exact $V { $($f),+ } $tr, $m, $atr, $am, $op;
+            i8, i16, i32, i128, isize, u8, u16, u32, u64, u128, usize);
+    };
+    (@scalar_exact $V:ident $fields:tt $tr:ident, $m:ident, $atr:ident, $am:ident, $op:tt; $($t:ty),+) => {$(
+        $crate::vec2::impl_vec_ops!(@scalar_one $V $fields $tr, $m, $atr, $am, $op; $t);
+    )+};
+    (@scalar_one $V:ident { $($f:ident),+ } $tr:ident, $m:ident, $atr:ident, $am:ident, $op:tt; $t:ty) => {
+        impl std::ops::$tr<$t> for $V<$t> {
+            type Output = $V<$t>;
+            #[inline]
+            fn $m(self, rhs: $t) -> Self::Output {
+                $V { $($f: self.$f $op rhs),+ }
+            }
+        }
+
+        impl std::ops::$tr<$t> for &$V<$t> {
+            type Output = $V<$t>;
+            #[inline]
+            fn $m(self, rhs: $t) -> Self::Output {
+                *self $op rhs
+            }
+        }
+
+        impl std::ops::$atr<$t> for $V<$t> {
+            #[inline]
+            fn $am(&mut self, rhs: $t) {
+                *self = *self $op rhs;
+            }
+        }
+    };
+}
+
+pub(crate) use impl_vec_ops;
+
+impl_vec_ops!(Vec2T { x, y });
+
 macro_rules! impl_from_tuple {
-    ($t:ty) => {
-        impl From<($t, $t)> for Vec2 {
+    ($($t:ty),+) => {$(
+        impl<T> From<($t, $t)> for Vec2T<T>
+        where
+            T: TryFrom<$t>,
+            T::Error: std::fmt::Debug,
+        {
             #[inline]
             fn from((x, y): ($t, $t)) -> Self {
                 Self {
@@ -149,39 +416,29 @@ macro_rules! impl_from_tuple {
                 }
             }
         }
-    };
-    ($t:ty, $($tail:ty),+) => {
-        impl_from_tuple!($t);
-        impl_from_tuple!($($tail),+);
-    };
+    )+};
 }
 
 impl_from_tuple!(isize, i64, i32, i16, i8, usize, u64, u32, u16, u8);
 
 macro_rules! impl_into_tuple {
-    ($t:ty) => {
-        impl From<Vec2> for ($t, $t) {
+    ($($t:ty),+) => {$(
+        impl<T> From<Vec2T<T>> for ($t, $t)
+        where
+            T: TryInto<$t>,
+            T::Error: std::fmt::Debug,
+        {
             #[inline]
-            fn from(Vec2 { x, y }: Vec2) -> Self {
+            fn from(Vec2T { x, y }: Vec2T<T>) -> Self {
                 (x.try_into().unwrap(), y.try_into().unwrap())
             }
         }
-    };
-    ($t:ty, $($tail:ty),+) => {
-        impl_into_tuple!($t);
-        impl_into_tuple!($($tail),+);
-    };
+    )+};
 }
 
 impl_into_tuple!(isize, i64, i32, i16, i8, usize, u64, u32, u16, u8);
 
-impl From<&Vec2> for Vec2 {
-    fn from(value: &Vec2) -> Self {
-        *value
-    }
-}
-
-impl From<Cardinal> for Vec2 {
+impl<T: Signed> From<Cardinal> for Vec2T<T> {
     #[inline]
     fn from(value: Cardinal) -> Self {
         match value {
@@ -193,15 +450,85 @@ impl From<Cardinal> for Vec2 {
     }
 }
 
-impl TryFrom<Vec2> for Cardinal {
+/// Converts an axis-aligned vector into the direction it points in.
+///
+/// Any non-zero vector along a single axis is accepted, so this is the
+/// inverse of `From<Cardinal> for Vec2` for unit vectors.
+impl<T: Signed> TryFrom<Vec2T<T>> for Cardinal {
     type Error = String;
-    fn try_from(value: Vec2) -> Result<Self, Self::Error> {
+    fn try_from(value: Vec2T<T>) -> Result<Self, Self::Error> {
+        let zero = T::ZERO;
         match value {
-            Vec2 { x, y: 0 } if x > 0 => Ok(Cardinal::East),
-            Vec2 { x, y: 0 } if x < 0 => Ok(Cardinal::West),
-            Vec2 { x: 0, y } if y > 0 => Ok(Cardinal::North),
-            Vec2 { x: 0, y } if y < 0 => Ok(Cardinal::West),
+            Vec2T { x, y } if y == zero && x > zero => Ok(Cardinal::East),
+            Vec2T { x, y } if y == zero && x < zero => Ok(Cardinal::West),
+            Vec2T { x, y } if x == zero && y > zero => Ok(Cardinal::South),
+            Vec2T { x, y } if x == zero && y < zero => Ok(Cardinal::North),
             _ => Err(format!("{value} is not a cardinal direction")),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn arithmetic() {
+        let a = Vec2::new(7, -3);
+        let b = Vec2::new(2, 5);
+        assert_eq!(-a, Vec2::new(-7, 3));
+        assert_eq!(a + b, Vec2::new(9, 2));
+        assert_eq!(a - b, Vec2::new(5, -8));
+        assert_eq!([a].iter().map(|v| v - b).next(), Some(Vec2::new(5, -8)));
+        assert_eq!(a * b, Vec2::new(14, -15));
+        assert_eq!(a * 2, Vec2::new(14, -6));
+        assert_eq!(a * 2u8, Vec2::new(14, -6));
+        assert_eq!(&a / 2i32, Vec2::new(3, -1));
+        assert_eq!(Vec2T::<u32>::new(7, 3) * 2, Vec2T::new(14, 6));
+        assert_eq!(a / 2, Vec2::new(3, -1));
+        assert_eq!(a % b, Vec2::new(1, -3));
+        assert_eq!(a.rem_euclid(b), Vec2::new(1, 2));
+        assert_eq!(a.rem_euclid((4, 4)), Vec2::new(3, 1));
+
+        let mut c = a;
+        c -= b;
+        c /= 5;
+        c %= 3;
+        assert_eq!(c, Vec2::new(1, -1));
+
+        assert_eq!(a.min(b), Vec2::new(2, -3));
+        assert_eq!(a.max(b), Vec2::new(7, 5));
+        assert_eq!(a.clamp(Vec2::new(0, 0), Vec2::new(5, 5)), Vec2::new(5, 0));
+        assert_eq!(a.abs(), Vec2::new(7, 3));
+        assert_eq!(a.signum(), Vec2::new(1, -1));
+        assert_eq!([a, b].iter().sum::<Vec2>(), Vec2::new(9, 2));
+        assert_eq!([a, b].into_iter().sum::<Vec2>(), Vec2::new(9, 2));
+    }
+
+    #[test]
+    fn geometry() {
+        let a = Vec2::new(1, 2);
+        let b = Vec2::new(-3, 5);
+        assert_eq!(a.manhattan(&b), 7);
+        assert_eq!(a.chebyshev(&b), 4);
+        assert_eq!(a.euclidean_sq(&b), 25);
+        assert_eq!(Vec2::up().rotate_cw(), Vec2::right());
+        assert_eq!(Vec2::up().rotate_ccw(), Vec2::left());
+        assert_eq!(a.perp().dot(&a), 0);
+        assert_eq!(Vec2::right().cross(&Vec2::down()), 1);
+        assert_eq!(Vec2::new(-4, 6).reduced(), Vec2::new(-2, 3));
+        assert_eq!(Vec2::new(0, -5).reduced(), Vec2::new(0, -1));
+
+        let diamond: Vec<_> = a.within_manhattan(2).collect();
+        assert_eq!(diamond.len(), 13);
+        assert!(diamond.iter().all(|v| v.manhattan(&a) <= 2));
+        assert_eq!(a.within_chebyshev(1).count(), 9);
+
+        let line: Vec<_> = Vec2::new(0, 0).line_to(Vec2::new(6, -4)).collect();
+        assert_eq!(
+            line,
+            vec![Vec2::new(0, 0), Vec2::new(3, -2), Vec2::new(6, -4)]
+        );
+        assert_eq!(a.line_to(a).collect::<Vec<_>>(), vec![a]);
+    }
+}
@@ -0,0 +1,185 @@
+use crate::num::{Int, Signed};
+
+/// The signed three-dimensional vector used by [`Grid3`](crate::Grid3).
+pub type Vec3 = Vec3T<i64>;
+
+/// A three-dimensional vector, generic over the integer type.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Vec3T<T> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+impl<T> Vec3T<T> {
+    #[inline]
+    #[must_use]
+    pub const fn new(x: T, y: T, z: T) -> Self {
+        Self { x, y, z }
+    }
+}
+
+impl<T: Int> Vec3T<T> {
+    /// Component-wise `rem_euclid`.
+    #[inline]
+    #[must_use]
+    pub fn rem_euclid(self, rhs: impl Into<Vec3T<T>>) -> Self {
+        let rhs = rhs.into();
+        Vec3T {
+            x: self.x.rem_euclid(rhs.x),
+            y: self.y.rem_euclid(rhs.y),
+            z: self.z.rem_euclid(rhs.z),
+        }
+    }
+
+    /// Component-wise minimum.
+    #[inline]
+    #[must_use]
+    pub fn min(self, rhs: Vec3T<T>) -> Self {
+        Vec3T {
+            x: self.x.min(rhs.x),
+            y: self.y.min(rhs.y),
+            z: self.z.min(rhs.z),
+        }
+    }
+
+    /// Component-wise maximum.
+    #[inline]
+    #[must_use]
+    pub fn max(self, rhs: Vec3T<T>) -> Self {
+        Vec3T {
+            x: self.x.max(rhs.x),
+            y: self.y.max(rhs.y),
+            z: self.z.max(rhs.z),
+        }
+    }
+
+    /// Returns the Manhattan (taxicab) distance to `other`.
+    #[inline]
+    pub fn manhattan(&self, other: &Vec3T<T>) -> T {
+        self.x.abs_diff(other.x) + self.y.abs_diff(other.y) + self.z.abs_diff(other.z)
+    }
+
+    /// Returns the Chebyshev (chessboard) distance to `other`.
+    #[inline]
+    pub fn chebyshev(&self, other: &Vec3T<T>) -> T {
+        self.x
+            .abs_diff(other.x)
+            .max(self.y.abs_diff(other.y))
+            .max(self.z.abs_diff(other.z))
+    }
+
+    /// Returns the square of the Euclidean distance to `other`.
+    #[inline]
+    pub fn euclidean_sq(&self, other: &Vec3T<T>) -> T {
+        let (x, y, z) = (
+            self.x.abs_diff(other.x),
+            self.y.abs_diff(other.y),
+            self.z.abs_diff(other.z),
+        );
+        x * x + y * y + z * z
+    }
+
+    #[inline]
+    pub fn dot(&self, other: &Vec3T<T>) -> T {
+        self.x * other.x + self.y * other.y + self.z * other.z
+    }
+}
+
+impl<T: Signed> Vec3T<T> {
+    #[inline]
+    #[must_use]
+    pub fn abs(self) -> Self {
+        Vec3T {
+            x: self.x.abs(),
+            y: self.y.abs(),
+            z: self.z.abs(),
+        }
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn signum(self) -> Self {
+        Vec3T {
+            x: self.x.signum(),
+            y: self.y.signum(),
+            z: self.z.signum(),
+        }
+    }
+
+    #[inline]
+    #[must_use]
+    pub fn cross(&self, other: &Vec3T<T>) -> Self {
+        Vec3T {
+            x: self.y * other.z - self.z * other.y,
+            y: self.z * other.x - self.x * other.z,
+            z: self.x * other.y - self.y * other.x,
+        }
+    }
+
+    /// Iterates over the six face-adjacent points.
+    pub fn neighbours(self) -> impl Iterator<Item = Vec3T<T>> {
+        let (o, i) = (T::ZERO, T::ONE);
+        [
+            Vec3T::new(i, o, o),
+            Vec3T::new(-i, o, o),
+            Vec3T::new(o, i, o),
+            Vec3T::new(o, -i, o),
+            Vec3T::new(o, o, i),
+            Vec3T::new(o, o, -i),
+        ]
+        .into_iter()
+        .map(move |offset| self + offset)
+    }
+}
+
+impl<T: std::fmt::Display> std::fmt::Display for Vec3T<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({}, {}, {})", self.x, self.y, self.z)
+    }
+}
+
+crate::vec2::impl_vec_ops!(Vec3T { x, y, z });
+
+macro_rules! impl_from_tuple {
+    ($($t:ty),+) => {$(
+        impl<T> From<($t, $t, $t)> for Vec3T<T>
+        where
+            T: TryFrom<$t>,
+            T::Error: std::fmt::Debug,
+        {
+            #[inline]
+            fn from((x, y, z): ($t, $t, $t)) -> Self {
+                Self {
+                    x: x.try_into().unwrap(),
+                    y: y.try_into().unwrap(),
+                    z: z.try_into().unwrap(),
+                }
+            }
+        }
+    )+};
+}
+
+impl_from_tuple!(isize, i64, i32, i16, i8, usize, u64, u32, u16, u8);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn arithmetic_and_distance() {
+        let a = Vec3::new(1, -2, 3);
+        let b = Vec3::new(4, 0, -1);
+        assert_eq!(a + b, Vec3::new(5, -2, 2));
+        assert_eq!(-a * 2, Vec3::new(-2, 4, -6));
+        assert_eq!(a.manhattan(&b), 9);
+        assert_eq!(a.chebyshev(&b), 4);
+        assert_eq!(a.euclidean_sq(&b), 29);
+        assert_eq!(a.cross(&b).dot(&a), 0);
+        assert_eq!(a.neighbours().filter(|n| n.manhattan(&a) == 1).count(), 6);
+        assert_eq!(
+            Vec3T::<u8>::from((1, 2, 3)).manhattan(&Vec3T::new(3, 2, 1)),
+            4
+        );
+    }
+}
@@ -0,0 +1,360 @@
+//! Terminal playback of grid simulations.
+//!
+//! Solutions report their progress through a [`Recorder`]. Passing `()`
+//! as the recorder discards every frame without building it, so the
+//! same code can run with visualisation disabled at no cost. Recording
+//! into a `Vec<Frame<T>>` instead produces a [`FrameSource`] that can be
+//! replayed with a [`Player`].
+//!
+//! Player controls: `space` play/pause, `n`/`.` step forward, `p`/`,`
+//! step back, `+`/`-` change speed, `g<frame><enter>` jump to frame and
+//! `q` quit.
+
+use crate::{Grid, Vec2};
+use std::io::{IsTerminal, Read, Write};
+use std::process::{Command, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc};
+use std::time::Duration;
+
+/// A single step of a simulation.
+#[derive(Clone, Debug)]
+pub enum Frame<T> {
+    /// The complete state of the grid.
+    Snapshot(Grid<T>),
+    /// The cells which changed since the previous frame.
+    Diff(Vec<(Vec2, T)>),
+}
+
+/// A source of frames to be replayed by a [`Player`].
+pub trait FrameSource<T> {
+    fn next_frame(&mut self) -> Option<Frame<T>>;
+}
+
+impl<T, I> FrameSource<T> for I
+where
+    I: Iterator<Item = Frame<T>>,
+{
+    #[inline]
+    fn next_frame(&mut self) -> Option<Frame<T>> {
+        self.next()
+    }
+}
+
+/// Receives frames from a running simulation.
+///
+/// Frames are passed as closures so that they are never built when the
+/// recorder discards them.
+pub trait Recorder<T> {
+    fn record<F>(&mut self, frame: F)
+    where
+        F: FnOnce() -> Frame<T>;
+}
+
+/// Discards all frames.
+impl<T> Recorder<T> for () {
+    #[inline(always)]
+    fn record<F>(&mut self, _: F)
+    where
+        F: FnOnce() -> Frame<T>,
+    {
+    }
+}
+
+impl<T> Recorder<T> for Vec<Frame<T>> {
+    #[inline]
+    fn record<F>(&mut self, frame: F)
+    where
+        F: FnOnce() -> Frame<T>,
+    {
+        self.push(frame());
+    }
+}
+
+/// Replays frames on top of an initial grid.
+///
+/// Every frame is stored as a list of cell changes. Applying a change
+/// swaps the stored value with the one in the grid, so the same list
+/// serves to step both forwards and backwards.
+pub struct Player<T, F> {
+    grid: Grid<T>,
+    glyph: F,
+    frames: Vec<Vec<(Vec2, T)>>,
+    frame: usize,
+    fps: u32,
+}
+
+impl<T, F> Player<T, F>
+where
+    T: Clone + PartialEq,
+    F: Fn(&T) -> char,
+{
+    /// Creates a new `Player` starting from `initial`, using `glyph` to
+    /// draw each cell.
+    pub fn new(initial: Grid<T>, glyph: F) -> Self {
+        Self {
+            grid: initial,
+            glyph,
+            frames: vec![],
+            frame: 0,
+            fps: 10,
+        }
+    }
+
+    /// Sets the initial playback speed in frames per second.
+    pub fn fps(mut self, fps: u32) -> Self {
+        self.fps = fps.max(1);
+        self
+    }
+
+    /// Returns the current frame number.
+    pub fn frame(&self) -> usize {
+        self.frame
+    }
+
+    /// Returns the grid as of the current frame.
+    pub fn grid(&self) -> &Grid<T> {
+        &self.grid
+    }
+
+    /// Advances one frame, pulling a new one from `source` if needed.
+    ///
+    /// Returns false if the source has been exhausted.
+    pub fn step_forward(&mut self, source: &mut impl FrameSource<T>) -> bool {
+        if self.frame == self.frames.len() {
+            let changes = match source.next_frame() {
+                None => return false,
+                Some(Frame::Diff(changes)) => changes,
+                Some(Frame::Snapshot(grid)) => {
+                    assert_eq!(
+                        (grid.cols(), grid.rows()),
+                        (self.grid.cols(), self.grid.rows())
+                    );
+                    grid.iter_pos()
+                        .filter(|&pos| grid[pos] != self.grid[pos])
+                        .map(|pos| (pos, grid[pos].clone()))
+                        .collect()
+                }
+            };
+            self.frames.push(changes);
+        }
+        self.apply(self.frame, false);
+        self.frame += 1;
+        true
+    }
+
+    /// Steps back one frame. Returns false if already at the start.
+    pub fn step_back(&mut self) -> bool {
+        if self.frame == 0 {
+            return false;
+        }
+        self.frame -= 1;
+        self.apply(self.frame, true);
+        true
+    }
+
+    /// Moves to the specified frame, or the last available frame if
+    /// the source runs out first.
+    pub fn seek(&mut self, source: &mut impl FrameSource<T>, frame: usize) {
+        while self.frame > frame && self.step_back() {}
+        while self.frame < frame && self.step_forward(source) {}
+    }
+
+    /// Replays `source` in the terminal.
+    ///
+    /// When stdin is not a terminal the frames are played through once
+    /// without any controls. Otherwise keys are read from stdin until
+    /// the player is closed with `q`.
+    pub fn play(&mut self, mut source: impl FrameSource<T>) {
+        let interactive = std::io::stdin().is_terminal() && std::io::stdout().is_terminal();
+        if !interactive {
+            println!("frame {}\n{}", self.frame, self.grid.render(&self.glyph));
+            while self.step_forward(&mut source) {
+                std::thread::sleep(self.delay());
+                println!("frame {}\n{}", self.frame, self.grid.render(&self.glyph));
+            }
+            return;
+        }
+
+        // Dropped in reverse order, stopping the key reader before the
+        // terminal leaves raw mode.
+        let _raw = RawMode::enable();
+        let keys = KeyReader::spawn();
+        let mut playing = true;
+        let mut target: Option<usize> = None;
+
+        self.draw(playing);
+        loop {
+            let key = match playing {
+                true => keys.keys.recv_timeout(self.delay()),
+                false => keys
+                    .keys
+                    .recv()
+                    .map_err(|_| mpsc::RecvTimeoutError::Disconnected),
+            };
+            match key {
+                Ok(b'q') => break,
+                Ok(b' ') => playing = !playing,
+                Ok(b'n' | b'.') => {
+                    playing = false;
+                    self.step_forward(&mut source);
+                }
+                Ok(b'p' | b',') => {
+                    playing = false;
+                    self.step_back();
+                }
+                Ok(b'+' | b'=') => self.fps = (self.fps * 2).min(1000),
+                Ok(b'-') => self.fps = (self.fps / 2).max(1),
+                Ok(b'g') => target = Some(0),
+                Ok(digit @ b'0'..=b'9') => {
+                    if let Some(target) = target.as_mut() {
+                        *target = *target * 10 + (digit - b'0') as usize;
+                    }
+                }
+                Ok(b'\n' | b'\r') => {
+                    if let Some(target) = target.take() {
+                        playing = false;
+                        self.seek(&mut source, target);
+                    }
+                }
+                Ok(_) => {}
+                Err(mpsc::RecvTimeoutError::Timeout) => {
+                    if playing && !self.step_forward(&mut source) {
+                        playing = false;
+                    }
+                }
+                Err(mpsc::RecvTimeoutError::Disconnected) => break,
+            }
+            self.draw(playing);
+        }
+    }
+
+    fn delay(&self) -> Duration {
+        Duration::from_secs(1) / self.fps
+    }
+
+    fn apply(&mut self, frame: usize, reverse: bool) {
+        let changes = &mut self.frames[frame];
+        let swap = |(pos, value): &mut (Vec2, T)| std::mem::swap(&mut self.grid[*pos], value);
+        match reverse {
+            true => changes.iter_mut().rev().for_each(swap),
+            false => changes.iter_mut().for_each(swap),
+        }
+    }
+
+    fn draw(&self, playing: bool) {
+        let mut stdout = std::io::stdout().lock();
+        let state = if playing { "playing" } else { "paused" };
+        _ = write!(
+            stdout,
+            "\x1b[H\x1b[2J{}frame {} ({} fps, {state})\r\n",
+            self.grid
+                .render(&self.glyph)
+                .to_string()
+                .replace('\n', "\r\n"),
+            self.frame,
+            self.fps,
+        );
+        _ = stdout.flush();
+    }
+}
+
+/// Puts the terminal into unbuffered, no-echo mode until dropped.
+///
+/// Reads from stdin return after at most a tenth of a second even
+/// without input, so that the [`KeyReader`] can notice when to stop.
+struct RawMode;
+
+impl RawMode {
+    fn enable() -> Self {
+        stty(&["-icanon", "-echo", "min", "0", "time", "1"]);
+        print!("\x1b[?25l");
+        Self
+    }
+}
+
+impl Drop for RawMode {
+    fn drop(&mut self) {
+        stty(&["icanon", "echo"]);
+        println!("\x1b[?25h");
+    }
+}
+
+fn stty(args: &[&str]) {
+    _ = Command::new("stty")
+        .args(args)
+        .stdin(Stdio::inherit())
+        .status();
+}
+
+/// Forwards each byte read from stdin over a channel until dropped.
+///
+/// Must be dropped while [`RawMode`] is enabled, so that the thread's
+/// reads time out and it can be stopped without consuming any more of
+/// the caller's input.
+struct KeyReader {
+    keys: mpsc::Receiver<u8>,
+    stop: Arc<AtomicBool>,
+    thread: Option<std::thread::JoinHandle<()>>,
+}
+
+impl KeyReader {
+    fn spawn() -> Self {
+        let (tx, keys) = mpsc::channel();
+        let stop = Arc::new(AtomicBool::new(false));
+        let stopped = Arc::clone(&stop);
+        let thread = std::thread::spawn(move || {
+            let mut byte = [0];
+            while !stopped.load(Ordering::Relaxed) {
+                match std::io::stdin().read(&mut byte) {
+                    Ok(0) => {}
+                    Ok(_) if tx.send(byte[0]).is_ok() => {}
+                    _ => break,
+                }
+            }
+        });
+        Self {
+            keys,
+            stop,
+            thread: Some(thread),
+        }
+    }
+}
+
+impl Drop for KeyReader {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+        if let Some(thread) = self.thread.take() {
+            _ = thread.join();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::gridify_ascii;
+
+    #[test]
+    fn player_seek() {
+        let initial = gridify_ascii("...".lines());
+        let mut frames = vec![];
+        frames.record(|| Frame::Diff(vec![(Vec2::new(0, 0), b'#')]));
+        frames.record(|| Frame::Snapshot(gridify_ascii(".##".lines())));
+        frames.record(|| Frame::Diff(vec![(Vec2::new(0, 0), b'O'), (Vec2::new(0, 0), b'@')]));
+        ().record(|| -> Frame<u8> { unreachable!() });
+
+        let mut source = frames.into_iter();
+        let mut player = Player::new(initial, |&v| v as char);
+        player.seek(&mut source, 2);
+        assert_eq!(player.grid().to_string(), ".##\n");
+        player.seek(&mut source, 10);
+        assert_eq!(player.frame(), 3);
+        assert_eq!(player.grid().to_string(), "@##\n");
+        player.seek(&mut source, 1);
+        assert_eq!(player.grid().to_string(), "#..\n");
+        player.seek(&mut source, 0);
+        assert_eq!(player.grid().to_string(), "...\n");
+        assert!(!player.step_back());
+    }
+}
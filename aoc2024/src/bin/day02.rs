@@ -25,21 +25,19 @@ fn part2(input: &str) -> impl std::fmt::Display {
             if master_samples.is_empty() {
                 return false;
             }
-            (0..master_samples.len())
-                .map(|index| {
-                    let mut samples = master_samples.clone();
-                    if index < samples.len() {
-                        samples.remove(index);
-                    }
-                    if samples.is_empty() || !monotonic(samples.iter()) {
-                        return false;
-                    }
-                    samples
-                        .windows(2)
-                        .map(|window| window[0].abs_diff(window[1]))
-                        .all(|delta| (1..=3).contains(&delta) && delta != 0)
-                })
-                .any(|v| v)
+            (0..master_samples.len()).any(|index| {
+                let mut samples = master_samples.clone();
+                if index < samples.len() {
+                    samples.remove(index);
+                }
+                if samples.is_empty() || !monotonic(samples.iter()) {
+                    return false;
+                }
+                samples
+                    .windows(2)
+                    .map(|window| window[0].abs_diff(window[1]))
+                    .all(|delta| (1..=3).contains(&delta) && delta != 0)
+            })
         })
         .count()
 }
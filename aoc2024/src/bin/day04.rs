@@ -1,82 +1,48 @@
 use aoc::*;
 
-fn part1(input: &str) -> impl std::fmt::Display {
-    let grid = gridify_ascii(input.lines());
-    let mut count = 0;
-    for Vec2 { x, y } in grid.iter_pos() {
-        let mut search = vec![
-            [
-                Vec2::new(x, y),
-                Vec2::new(x + 1, y),
-                Vec2::new(x + 2, y),
-                Vec2::new(x + 3, y),
-            ],
-            [
-                Vec2::new(x, y),
-                Vec2::new(x, y + 1),
-                Vec2::new(x, y + 2),
-                Vec2::new(x, y + 3),
-            ],
-            [
-                Vec2::new(x, y),
-                Vec2::new(x + 1, y + 1),
-                Vec2::new(x + 2, y + 2),
-                Vec2::new(x + 3, y + 3),
-            ],
-        ];
-        if y >= 3 {
-            search.push([
-                Vec2::new(x, y),
-                Vec2::new(x + 1, y - 1),
-                Vec2::new(x + 2, y - 2),
-                Vec2::new(x + 3, y - 3),
-            ]);
-        }
-
-        for search in search {
-            let Some(found) = map(&grid, &search) else {
-                continue;
-            };
-            if ["XMAS", "SAMX"].contains(&found.as_str()) {
-                count += 1;
-            }
-        }
-    }
-    count
+/// Returns the grid and its rotations by 90, 180 and 270 degrees, so
+/// that a search in one direction covers all four.
+fn rotations(grid: Grid<u8>) -> impl Iterator<Item = Grid<u8>> {
+    std::iter::successors(Some(grid), |grid| Some(grid.rotate_cw())).take(4)
 }
 
-fn part2(input: &str) -> impl std::fmt::Display {
-    let grid = gridify_ascii(input.lines());
-    let mut count = 0;
-    for Vec2 { x, y } in grid.iter_pos() {
-        let pattern = [
-            Vec2::new(x, y),         // top-left
-            Vec2::new(x + 2, y),     // top-right
-            Vec2::new(x, y + 2),     // bottom-left
-            Vec2::new(x + 2, y + 2), // bottom-right
-            Vec2::new(x + 1, y + 1), // middle
-        ];
-        let Some(found) = map(&grid, &pattern) else {
-            continue;
-        };
-        if ["MMSSA", "MSMSA", "SSMMA", "SMSMA"].contains(&found.as_str()) {
-            count += 1;
-        }
-    }
+/// Returns whether every `(offset, value)` pair matches the grid when
+/// offset from `pos`.
+fn matches(grid: &Grid<u8>, pos: Vec2, pattern: &[(Vec2, u8)]) -> bool {
+    pattern
+        .iter()
+        .all(|&(offset, value)| grid.get(&(pos + offset)) == Some(&value))
+}
 
-    count
+fn part1(input: &str) -> impl std::fmt::Display {
+    let patterns = [Vec2::new(1, 0), Vec2::new(1, 1)].map(|step| {
+        let letters = b"XMAS".iter().zip(0..);
+        letters.map(|(&c, i)| (step * i, c)).collect::<Vec<_>>()
+    });
+    rotations(gridify_ascii(input.lines()))
+        .map(|grid| {
+            grid.iter_pos()
+                .map(|pos| patterns.iter().filter(|p| matches(&grid, pos, p)).count())
+                .sum::<usize>()
+        })
+        .sum::<usize>()
 }
-fn map(grid: &Grid<u8>, positions: &[Vec2]) -> Option<String> {
-    let bytes: Vec<_> = positions
-        .iter()
-        .filter_map(|pos| grid.get(pos).copied())
-        .collect();
 
-    if bytes.len() == positions.len() {
-        Some(String::from_utf8(bytes).unwrap())
-    } else {
-        None
-    }
+fn part2(input: &str) -> impl std::fmt::Display {
+    let pattern = [
+        (Vec2::new(0, 0), b'M'), // top-left
+        (Vec2::new(2, 0), b'M'), // top-right
+        (Vec2::new(1, 1), b'A'), // middle
+        (Vec2::new(0, 2), b'S'), // bottom-left
+        (Vec2::new(2, 2), b'S'), // bottom-right
+    ];
+    rotations(gridify_ascii(input.lines()))
+        .map(|grid| {
+            grid.iter_pos()
+                .filter(|&pos| matches(&grid, pos, &pattern))
+                .count()
+        })
+        .sum::<usize>()
 }
 
 aoc::setup! {
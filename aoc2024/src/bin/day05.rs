@@ -1,107 +1,66 @@
-use aoc::*;
-use std::{
-    cmp::Ordering,
-    collections::{HashMap, HashSet},
-    rc::Rc,
-};
+use aoc::graph::Graph;
+use std::collections::HashSet;
 
-struct Updates {
-    updates: Vec<Vec<usize>>,
-    rules: Rc<HashMap<usize, HashSet<usize>>>,
-}
-
-impl Updates {
-    fn extract(input: &str) -> Self {
-        let mut lines = input.lines().map(|line| line.trim());
-        let mut rules: HashMap<_, HashSet<_>> = HashMap::new();
-        for (left, right) in take_pairs!(&mut lines, '|') {
-            rules.entry(left).or_default();
-            rules
-                .entry(right)
-                .and_modify(|before_set| {
-                    before_set.insert(left);
-                })
-                .or_insert([left].into());
-        }
-        Self {
-            rules: Rc::new(rules),
-            updates: take_lists!(&mut lines, ',').collect(),
-        }
-    }
-
-    fn is_valid(&self, update: &[usize]) -> bool {
-        update
-            .iter()
-            .scan(HashSet::with_capacity(update.len()), |seen, page| {
-                if !seen.iter().all(|n| self.rules[page].contains(n)) {
-                    return Some(false);
-                }
-                seen.insert(*page);
-                Some(true)
-            })
-            .all(|v| v)
-    }
+type Parsed = (Vec<(usize, usize)>, Vec<Vec<usize>>);
 
-    fn valid(&self) -> Self {
-        Self {
-            updates: self
-                .updates
-                .iter()
-                .filter(|update| self.is_valid(update))
-                .cloned()
-                .collect(),
-            rules: Rc::clone(&self.rules),
-        }
-    }
-
-    fn invalid(&self) -> Self {
-        Self {
-            updates: self
-                .updates
-                .iter()
-                .filter(|update| !self.is_valid(update))
-                .cloned()
-                .collect(),
-            rules: Rc::clone(&self.rules),
-        }
-    }
+/// Returns whether no rule requires a later page of the update to be
+/// printed before an earlier one.
+fn is_ordered(rules: &HashSet<(usize, usize)>, update: &[usize]) -> bool {
+    update
+        .iter()
+        .enumerate()
+        .all(|(i, &a)| update[i + 1..].iter().all(|&b| !rules.contains(&(b, a))))
+}
 
-    fn sum_middles(&self) -> usize {
-        self.updates
+/// Returns the pages of an update in an order allowed by the rules, or
+/// `None` if the rules for its pages contain a cycle.
+fn sorted(rules: &[(usize, usize)], update: &[usize]) -> Option<Vec<usize>> {
+    let mut graph = Graph::directed(
+        rules
             .iter()
-            .map(|update| {
-                assert_eq!(update.len() % 2, 1);
-                update[update.len() / 2]
-            })
-            .sum()
+            .copied()
+            .filter(|(a, b)| update.contains(a) && update.contains(b)),
+    );
+    for &page in update {
+        graph.add_node(page);
     }
+    let order = graph.topological_sort().ok()?;
+    Some(order.into_iter().map(|n| *graph.label(n)).collect())
+}
 
-    fn make_valid(mut self) -> Self {
-        for update in &mut self.updates {
-            update.sort_by(|a, b| {
-                if self.rules.get(a).is_some_and(|set| set.contains(b)) {
-                    Ordering::Greater
-                } else {
-                    Ordering::Less
-                }
-            });
-        }
-        self
-    }
+fn middle(update: &[usize]) -> usize {
+    assert_eq!(update.len() % 2, 1);
+    update[update.len() / 2]
 }
 
-fn part1(input: &str) -> impl std::fmt::Display {
-    let updates = Updates::extract(input);
-    updates.valid().sum_middles()
+fn part1((rules, updates): &Parsed) -> impl std::fmt::Display {
+    let rules: HashSet<_> = rules.iter().copied().collect();
+    updates
+        .iter()
+        .filter(|update| is_ordered(&rules, update))
+        .map(|update| middle(update))
+        .sum::<usize>()
 }
 
-fn part2(input: &str) -> impl std::fmt::Display {
-    let updates = Updates::extract(input);
-    updates.invalid().make_valid().sum_middles()
+fn part2((rules, updates): &Parsed) -> impl std::fmt::Display {
+    let rule_set: HashSet<_> = rules.iter().copied().collect();
+    updates
+        .iter()
+        .filter(|update| !is_ordered(&rule_set, update))
+        .map(|update| {
+            sorted(rules, update)
+                .unwrap_or_else(|| panic!("the rules for update {update:?} contain a cycle"))
+        })
+        .map(|sorted| middle(&sorted))
+        .sum::<usize>()
 }
 
 aoc::setup! {
-    day05;
+    day05, parse_input: Parsed = pair(
+        lines(pair(number(), tag("|"), number())),
+        blank_line(),
+        lines(sep_by(number(), tag(","))),
+    );
     part1 == 143,
     part2 == 123
 }
@@ -1,27 +1,23 @@
 use aoc::*;
 use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
-use std::collections::HashSet;
 
-fn explore(grid: &Grid<u8>, pos: Vec2) -> Option<HashSet<Vec2>> {
-    let mut visited = HashSet::new();
-    let mut visited2 = HashSet::new();
-    let mut cursor = grid.cursor(pos);
+fn explore(grid: &Overlay<u8>, mut pos: Vec2) -> Option<BitGrid> {
+    let mut visited = BitGrid::new(grid.cols(), grid.rows());
+    let mut visited2 = BitSet::with_capacity(grid.cols() * grid.rows() * 4);
     let mut direction = Cardinal::North;
     loop {
-        if !visited2.insert((cursor.pos(), direction)) {
+        let index = (pos.y as usize * grid.cols() + pos.x as usize) * 4 + direction.index();
+        if !visited2.insert(index) {
             return None;
         }
-        visited.insert(cursor.pos());
-        match cursor.peek(direction) {
+        visited.insert(pos);
+        let next = pos.translate(direction);
+        match grid.get(&next) {
             Some(b'#') => {
                 direction = direction.next_clockwise();
                 continue;
             }
-            Some(_) => {
-                if !cursor.step(direction) {
-                    break;
-                }
-            }
+            Some(_) => pos = next,
             None => break,
         }
     }
@@ -31,17 +27,17 @@ fn explore(grid: &Grid<u8>, pos: Vec2) -> Option<HashSet<Vec2>> {
 fn part1(input: &str) -> impl std::fmt::Display {
     let grid = gridify_ascii(input.lines());
     let start = grid.position(|&v| v == b'^').unwrap();
-    explore(&grid, start).unwrap().len()
+    explore(&grid.overlay(), start).unwrap().len()
 }
 
 fn part2(input: &str) -> impl std::fmt::Display {
     let grid = gridify_ascii(input.lines());
     let start = grid.position(|&v| v == b'^').unwrap();
-    let positions: Vec<_> = explore(&grid, start).unwrap().into_iter().collect();
+    let positions: Vec<_> = explore(&grid.overlay(), start).unwrap().iter().collect();
     let count = positions
         .par_iter()
         .map(|pos| {
-            let mut grid = grid.clone();
+            let mut grid = grid.overlay();
             grid.set(pos, b'#');
             grid
         })
@@ -1,5 +1,4 @@
 use aoc::*;
-use std::collections::HashSet;
 
 type Parsed = Grid<u8>;
 
@@ -11,7 +10,7 @@ fn part1(grid: &Parsed) -> impl std::fmt::Display {
     grid.position_all(|&v| v == b'0')
         .into_iter()
         .map(|head| {
-            let peaks: HashSet<_> = walk(grid.cursor(head), vec![])
+            let peaks: FastSet<_> = walk(grid.cursor(head), vec![])
                 .into_iter()
                 .map(|path| path[9])
                 .collect();
@@ -27,14 +26,14 @@ fn part2(grid: &Parsed) -> impl std::fmt::Display {
         .sum::<usize>()
 }
 
-fn walk(cursor: Cursor<u8>, mut path: Vec<Vec2>) -> HashSet<Vec<Vec2>> {
+fn walk(cursor: Cursor<u8>, mut path: Vec<Vec2>) -> FastSet<Vec<Vec2>> {
     let &value = cursor.value();
     path.push(cursor.pos());
     if value == b'9' {
         assert_eq!(path.len(), 10);
-        return HashSet::from_iter([path]);
+        return FastSet::from_iter([path]);
     }
-    let mut peaks = HashSet::new();
+    let mut peaks = FastSet::default();
     for direction in [
         Cardinal::North,
         Cardinal::East,
@@ -1,3 +1,4 @@
+use aoc::memo::Memo;
 use aoc::*;
 use std::collections::HashMap;
 
@@ -20,32 +21,30 @@ fn part2(stones: &Parsed) -> impl std::fmt::Display {
 }
 
 fn solve(stones: &Parsed, iterations: u64) -> u64 {
-    let mut stones = stones.clone();
+    let mut memo = Memo::new();
+    stones
+        .iter()
+        .map(|(&stone, &count)| count * blink(&mut memo, stone, iterations))
+        .sum()
+}
 
-    for _ in 0..iterations {
-        let mut new_stones = HashMap::with_capacity(stones.len());
-        for (key, count) in stones {
-            match key {
-                0 => *new_stones.entry(1).or_default() += count,
-                _ => {
-                    let digit_count = key.ilog10() + 1;
-                    if digit_count % 2 == 0 {
-                        *new_stones
-                            .entry(key % 10u64.pow(digit_count / 2))
-                            .or_default() += count;
-                        *new_stones
-                            .entry(key / 10u64.pow(digit_count / 2))
-                            .or_default() += count;
-                    } else {
-                        *new_stones.entry(key * 2024).or_default() += count;
-                    }
-                }
-            }
-        }
-        stones = new_stones;
+/// Returns the number of stones `stone` becomes after `iterations`.
+fn blink(memo: &mut Memo<(u64, u64), u64>, stone: u64, iterations: u64) -> u64 {
+    if iterations == 0 {
+        return 1;
     }
-
-    stones.into_values().sum()
+    memo.get_or_compute(&(stone, iterations), |memo| {
+        if stone == 0 {
+            return blink(memo, 1, iterations - 1);
+        }
+        let digit_count = stone.ilog10() + 1;
+        if digit_count % 2 == 0 {
+            let split = 10u64.pow(digit_count / 2);
+            blink(memo, stone % split, iterations - 1) + blink(memo, stone / split, iterations - 1)
+        } else {
+            blink(memo, stone * 2024, iterations - 1)
+        }
+    })
 }
 
 aoc::setup! {
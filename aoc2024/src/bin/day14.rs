@@ -1,10 +1,6 @@
-use std::{
-    cmp::Ordering,
-    collections::{BTreeMap, BTreeSet},
-};
+use std::collections::{BTreeMap, BTreeSet};
 
 use aoc::*;
-use regex::Regex;
 
 struct Robot {
     pos: Vec2,
@@ -14,54 +10,45 @@ struct Robot {
 type Parsed = Vec<Robot>;
 
 fn parse_input(input: &str) -> Parsed {
-    let re =
-        Regex::new(r#"p=(?<px>[0-9]+),(?<py>[0-9]+) v=(?<vx>\-?[0-9]+),(?<vy>\-?[0-9]+)"#).unwrap();
-    let extract = |line: &str| -> Option<Robot> {
-        re.captures(line).map(|caps| {
-            let (_, [px, py, vx, vy]) = caps.extract();
-            Robot {
-                pos: Vec2::new(parse(px), parse(py)),
-                vel: Vec2::new(parse(vx), parse(vy)),
-            }
+    input
+        .lines()
+        .map(|line| {
+            scan!(line, "p={},{} v={},{}" => |px: i64, py: i64, vx: i64, vy: i64| Robot {
+                pos: Vec2::new(px, py),
+                vel: Vec2::new(vx, vy),
+            })
+            .unwrap()
         })
-    };
-
-    input.lines().map(|line| extract(line).unwrap()).collect()
+        .collect()
 }
 
 fn part1(input: &Parsed, max: Vec2) -> impl std::fmt::Display {
     const SECONDS: i64 = 100;
 
+    let quadrants = Rect::new((0, 0), max).split_quadrants();
     let mut quads = [0, 0, 0, 0];
     for robot in input {
-        let x = (robot.pos.x + robot.vel.x * SECONDS).rem_euclid(max.x);
-        let y = (robot.pos.y + robot.vel.y * SECONDS).rem_euclid(max.y);
-        let quad = match (x.cmp(&(max.x / 2)), y.cmp(&(max.y / 2))) {
-            (Ordering::Equal, _) | (_, Ordering::Equal) => continue,
-            (Ordering::Less, Ordering::Less) => 0,
-            (Ordering::Greater, Ordering::Less) => 1,
-            (Ordering::Less, Ordering::Greater) => 2,
-            (Ordering::Greater, Ordering::Greater) => 3,
-        };
-
-        quads[quad] += 1;
+        let pos = (robot.pos + robot.vel * SECONDS).rem_euclid(max);
+        if let Some(quad) = quadrants.iter().position(|q| q.contains(&pos)) {
+            quads[quad] += 1;
+        }
     }
     quads.into_iter().product::<i64>()
 }
 
 fn part2(input: &Parsed, max: Vec2) {
     fn simulate(bots: &Parsed, seconds: i64, dims: Vec2) -> Vec<Vec2> {
-        let mut pos = vec![];
-        for robot in bots {
-            let x = (robot.pos.x + robot.vel.x * seconds).rem_euclid(dims.x);
-            let y = (robot.pos.y + robot.vel.y * seconds).rem_euclid(dims.y);
-            pos.push(Vec2::new(x, y));
-        }
-        pos
+        bots.iter()
+            .map(|robot| (robot.pos + robot.vel * seconds).rem_euclid(dims))
+            .collect()
     }
 
+    // Each robot's x repeats every `max.x` seconds and its y every `max.y`,
+    // so every state repeats within their least common multiple.
+    let period = math::lcm(max.x, max.y);
+
     let mut candidates: BTreeMap<usize, i64> = Default::default();
-    for i in 0..(max.x * max.y) {
+    for i in 0..period {
         let positions = simulate(input, i, max);
         let mut bot_adjacency_x = BTreeSet::new();
         let mut bot_adjacency_y = BTreeSet::new();
@@ -101,15 +88,17 @@ fn part2(input: &Parsed, max: Vec2) {
 fn main() {
     println!("day14");
     const INPUT: &str = include_str!("../input/day14.in");
-    let (input, pre) = aoc::load(INPUT);
+    let (input, pre, fingerprint) = aoc::load(INPUT);
     let trimmed_input = input.trim();
+    println!("{pre}input: {fingerprint:016x}");
 
     let (parsed, elapsed_parse) = aoc::time!(parse_input(trimmed_input));
-    println!("{pre}parse ({elapsed_parse:?})");
+    println!("parse ({elapsed_parse:?})");
 
     const DIMENSIONS: (usize, usize) = (101, 103);
-    let (part1, elapsed_part1) = aoc::time!(part1(&parsed, DIMENSIONS.into()));
+    let (part1, elapsed_part1) = aoc::time!(part1(&parsed, DIMENSIONS.into()).to_string());
     println!("part1: {part1} ({elapsed_part1:?})");
+    aoc::record_answer("day14", fingerprint, "part1", &part1, elapsed_part1);
 
     let (_, elapsed_part2) = aoc::time!(part2(&parsed, DIMENSIONS.into()));
     println!("part2: () ({elapsed_part2:?})");
@@ -1,79 +1,48 @@
 use aoc::*;
-use std::collections::{HashMap, HashSet};
 
-type Parsed = (Vec2, HashSet<Vec2>, HashSet<Vec2>, Vec<Cardinal>);
+type Parsed = (Grid<u8>, Vec<Cardinal>);
 
 fn parse_input(input: &str) -> Parsed {
-    let (map, moves) = input.split_once("\n\n").unwrap();
-
-    let mut position = Default::default();
-    let mut walls = HashSet::default();
-    let mut boxes = HashSet::default();
-    for (row, line) in map.lines().enumerate() {
-        for (column, &value) in line.as_bytes().iter().enumerate() {
-            let pos = Vec2::new(column as i64, row as i64);
-            match value {
-                b'#' => _ = walls.insert(pos),
-                b'O' => _ = boxes.insert(pos),
-                b'@' => position = pos,
-                _ => {}
-            }
-        }
-    }
+    let [map, moves] = sections(input).exactly().unwrap();
 
+    let map = gridify_ascii(map.lines());
     let moves = moves
         .as_bytes()
         .iter()
         .filter_map(|&x| Cardinal::from_ascii(x))
         .collect();
 
-    (position, walls, boxes, moves)
+    (map, moves)
 }
 
-fn part1((mut position, walls, boxes, moves): &Parsed) -> impl std::fmt::Display {
-    let mut boxes = boxes.clone();
+fn part1((map, moves): &Parsed) -> impl std::fmt::Display {
+    let mut map = map.clone();
+    let start = map.position(|&v| v == b'@').unwrap();
+    let mut robot = map.cursor_mut(start);
     for &direction in moves {
-        position = try_move(position, direction, walls, &mut boxes).unwrap_or(position);
+        robot.push(direction, &b'.', |&v| v != b'#');
     }
-    boxes
+    map.position_all(|&v| v == b'O')
         .into_iter()
         .map(|Vec2 { x, y }| x + y * 100)
         .sum::<i64>()
 }
 
-fn try_move(
-    position: Vec2,
-    direction: Cardinal,
-    walls: &HashSet<Vec2>,
-    boxes: &mut HashSet<Vec2>,
-) -> Option<Vec2> {
-    let next_position = position.translate(direction);
-    if walls.contains(&next_position) {
-        None
-    } else if boxes.contains(&next_position) {
-        let available = try_move(next_position, direction, walls, boxes)?;
-        boxes.insert(available);
-        boxes.remove(&next_position);
-        Some(next_position)
-    } else {
-        Some(next_position)
-    }
-}
-
-fn part2((start, old_walls, old_boxes, moves): &Parsed) -> impl std::fmt::Display {
-    let mut walls = HashSet::new();
-    for &Vec2 { x, y } in old_walls {
-        walls.insert(Vec2 { x: x * 2, y });
-        walls.insert(Vec2 { x: x * 2 + 1, y });
-    }
-
-    let mut boxes = HashMap::new();
-    for (id, &Vec2 { x, y }) in old_boxes.iter().enumerate() {
-        boxes.insert(Vec2 { x: x * 2, y }, id);
-        boxes.insert(Vec2 { x: x * 2 + 1, y }, id);
+fn part2((map, moves): &Parsed) -> impl std::fmt::Display {
+    let map = map.scale_horizontal_with(|&v| match v {
+        b'O' => [b'[', b']'],
+        b'@' => [b'@', b'.'],
+        _ => [v, v],
+    });
+    let walls: FastSet<_> = map.position_all(|&v| v == b'#').into_iter().collect();
+
+    let mut boxes = FastMap::default();
+    for (id, pos) in map.position_all(|&v| v == b'[').into_iter().enumerate() {
+        boxes.insert(pos, id);
+        boxes.insert(pos.translate(Cardinal::East), id);
     }
 
-    let mut position = Vec2::new(start.x * 2, start.y);
+    let mut position = map.position(|&v| v == b'@').unwrap();
     for &direction in moves {
         position = match direction {
             Cardinal::West | Cardinal::East => try_move_lr(position, direction, &walls, &mut boxes),
@@ -85,7 +54,7 @@ fn part2((start, old_walls, old_boxes, moves): &Parsed) -> impl std::fmt::Displa
     }
 
     let mut filtered = vec![];
-    let mut mapping: HashMap<usize, Vec<Vec2>> = HashMap::new();
+    let mut mapping: FastMap<usize, Vec<Vec2>> = FastMap::default();
     for (pos, id) in boxes {
         let positions = mapping.entry(id).or_default();
         positions.push(pos);
@@ -105,8 +74,8 @@ fn part2((start, old_walls, old_boxes, moves): &Parsed) -> impl std::fmt::Displa
 fn try_move_lr(
     position: Vec2,
     direction: Cardinal,
-    walls: &HashSet<Vec2>,
-    boxes: &mut HashMap<Vec2, usize>,
+    walls: &FastSet<Vec2>,
+    boxes: &mut FastMap<Vec2, usize>,
 ) -> Option<Vec2> {
     let next_position = position.translate(direction);
     if walls.contains(&next_position) {
@@ -124,16 +93,16 @@ fn try_move_lr(
 fn try_move_ud(
     position: Vec2,
     direction: Cardinal,
-    walls: &HashSet<Vec2>,
-    boxes: &mut HashMap<Vec2, usize>,
+    walls: &FastSet<Vec2>,
+    boxes: &mut FastMap<Vec2, usize>,
 ) -> Option<Vec2> {
     fn step(
         id: usize,
         pos1: Vec2,
         pos2: Vec2,
         direction: Cardinal,
-        walls: &HashSet<Vec2>,
-        boxes: &mut HashMap<Vec2, usize>,
+        walls: &FastSet<Vec2>,
+        boxes: &mut FastMap<Vec2, usize>,
         test: bool,
     ) -> Option<Vec2> {
         if boxes.get(&pos2).is_some_and(|&i| i == id) {
@@ -160,8 +129,8 @@ fn try_move_ud(
     fn inner(
         position: Vec2,
         direction: Cardinal,
-        walls: &HashSet<Vec2>,
-        boxes: &mut HashMap<Vec2, usize>,
+        walls: &FastSet<Vec2>,
+        boxes: &mut FastMap<Vec2, usize>,
         test: bool,
     ) -> Option<Vec2> {
         let next_position = position.translate(direction);
@@ -1,7 +1,7 @@
 use aoc::*;
 use std::{
     cmp::Reverse,
-    collections::{BinaryHeap, HashMap, HashSet, VecDeque},
+    collections::{BinaryHeap, VecDeque},
 };
 
 type Parsed = (Grid<u8>, Vec2, Vec2);
@@ -29,7 +29,7 @@ fn part2(parsed: &Parsed) -> impl std::fmt::Display {
         }
     }
 
-    let mut visited = HashSet::new();
+    let mut visited = BitGrid::like(&parsed.0);
     while let Some((score, pos, dir)) = queue.pop_front() {
         visited.insert(pos);
 
@@ -54,10 +54,11 @@ fn part2(parsed: &Parsed) -> impl std::fmt::Display {
     visited.len()
 }
 
-type ScoreMap = HashMap<(Vec2, Cardinal), u64>;
+type ScoreMap = FastMap<(Vec2, Cardinal), u64>;
 
 fn solve((grid, start, end): &Parsed) -> Option<(u64, ScoreMap)> {
-    let mut scores: ScoreMap = ScoreMap::with_capacity(grid.cols() * grid.rows());
+    let mut scores: ScoreMap =
+        ScoreMap::with_capacity_and_hasher(grid.cols() * grid.rows(), Default::default());
     let mut queue: BinaryHeap<Reverse<(u64, Vec2, Cardinal)>> = BinaryHeap::new();
     queue.push(Reverse((1000, *start, Default::default())));
 
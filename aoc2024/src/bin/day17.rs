@@ -3,14 +3,30 @@ use std::collections::VecDeque;
 
 type Parsed = ([u64; 3], Vec<u8>);
 
-fn parse_input(input: &str) -> Parsed {
-    let mut lines = input.lines();
-    let a = parse(lines.next().unwrap().split_at(12).1);
-    let b = parse(lines.next().unwrap().split_at(12).1);
-    let c = parse(lines.next().unwrap().split_at(12).1);
-    lines.next();
-    let program = parse_list!(lines.next().unwrap().split_at(9).1, ',');
-    ([a, b, c], program)
+fn parse_input(input: &str) -> Result<Parsed, ParseError> {
+    let lines: Vec<&str> = input.lines().collect();
+    let field = |number: usize, prefix: &str| {
+        let line = lines.get(number - 1).copied().unwrap_or_default();
+        match line.strip_prefix(prefix) {
+            Some(value) => Ok(value),
+            None => Err(ParseError::new(
+                number,
+                1,
+                line,
+                format!("expected {prefix:?}"),
+            )),
+        }
+    };
+
+    let mut registers = [0; 3];
+    for (i, name) in ["A", "B", "C"].into_iter().enumerate() {
+        let prefix = format!("Register {name}: ");
+        registers[i] = try_parse(field(i + 1, &prefix)?)
+            .map_err(|err| err.at_line(i + 1).offset_column(prefix.len()))?;
+    }
+    let program = try_parse_list!(field(5, "Program: ")?, ',')
+        .map_err(|err| err.at_line(5).offset_column("Program: ".len()))?;
+    Ok((registers, program))
 }
 
 fn part1((registers, program): &Parsed) -> impl std::fmt::Display {
@@ -77,7 +93,7 @@ fn run([mut a, mut b, mut c]: [u64; 3], program: &[u8]) -> Vec<u8> {
 }
 
 aoc::setup! {
-    day17, parse_input;
+    day17, parse_input?;
     part1 == "4,6,3,5,6,3,5,2,1,0",
     "day17-part2.in": part2 == 117440
 }
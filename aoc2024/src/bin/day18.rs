@@ -80,8 +80,9 @@ fn compute_distance(
 fn main() {
     println!("day18");
     const INPUT: &str = include_str!("../input/day18.in");
-    let (input, pre) = aoc::load(INPUT);
+    let (input, pre, fingerprint) = aoc::load(INPUT);
     let trimmed_input = input.trim();
+    println!("{pre}input: {fingerprint:016x}");
 
     let mut args = std::env::args().skip(2);
     let x: i64 = args
@@ -106,14 +107,16 @@ fn main() {
         .expect("Expected number of bytes to simulate");
 
     let (parsed, elapsed_parse) = aoc::time!(parse_input(trimmed_input));
-    println!("{pre}parse ({elapsed_parse:?})");
+    println!("parse ({elapsed_parse:?})");
 
     let dimensions: Vec2 = Vec2::new(x, y);
-    let (part1, elapsed_part1) = aoc::time!(part1(&parsed, dimensions, len));
+    let (part1, elapsed_part1) = aoc::time!(part1(&parsed, dimensions, len).to_string());
     println!("part1: {part1} ({elapsed_part1:?})");
+    aoc::record_answer("day18", fingerprint, "part1", &part1, elapsed_part1);
 
-    let (part2, elapsed_part2) = aoc::time!(part2(&parsed, dimensions));
+    let (part2, elapsed_part2) = aoc::time!(part2(&parsed, dimensions).to_string());
     println!("part2: {part2} ({elapsed_part2:?})");
+    aoc::record_answer("day18", fingerprint, "part2", &part2, elapsed_part2);
 }
 
 #[cfg(test)]
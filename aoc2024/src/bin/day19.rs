@@ -1,13 +1,17 @@
+use aoc::memo::{Memo, PerThread};
 use aoc::*;
 use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
 use regex::Regex;
-use std::collections::HashMap;
 
 type Parsed<'a> = (Vec<&'a str>, Vec<&'a str>);
 
-fn parse_input(input: &str) -> Parsed {
-    let (towels, designs) = input.split_once("\n\n").unwrap();
-    let mut towels: Vec<_> = towels.split(',').map(str::trim).collect();
+fn parse_input(input: &str) -> Parsed<'_> {
+    let [towels, designs] = sections(input).exactly().unwrap();
+    let mut towels: Vec<_> = towels
+        .lines()
+        .flat_map(|line| line.split(','))
+        .map(str::trim)
+        .collect();
     towels.sort();
     let designs = designs.lines().collect();
     (towels, designs)
@@ -19,32 +23,24 @@ fn part1((towels, designs): &Parsed) -> impl std::fmt::Display {
 }
 
 fn part2((towels, designs): &Parsed) -> impl std::fmt::Display {
+    let memos = PerThread::new();
     designs
         .par_iter()
-        .map(|design| count_solutions(towels, design))
+        .map(|design| memos.with(|memo| count_solutions(memo, towels, design)))
         .sum::<usize>()
 }
 
-fn count_solutions(towels: &[&str], design: &str) -> usize {
-    fn inner<'a>(towels: &[&str], design: &'a str, cache: &mut HashMap<&'a str, usize>) -> usize {
-        if design.is_empty() {
-            return 1;
-        }
-        if let Some(&c) = cache.get(design) {
-            return c;
-        }
-
-        let count = towels
+fn count_solutions<'a>(memo: &mut Memo<&'a str, usize>, towels: &[&str], design: &'a str) -> usize {
+    if design.is_empty() {
+        return 1;
+    }
+    memo.get_or_compute(&design, |memo| {
+        towels
             .iter()
             .filter(|&towel| design.starts_with(towel))
-            .map(|towel| inner(towels, &design[towel.len()..], cache))
-            .sum();
-
-        cache.insert(design, count);
-        count
-    }
-
-    inner(towels, design, &mut HashMap::new())
+            .map(|towel| count_solutions(memo, towels, &design[towel.len()..]))
+            .sum()
+    })
 }
 
 aoc::setup! {
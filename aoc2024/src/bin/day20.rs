@@ -1,5 +1,4 @@
 use aoc::*;
-use std::collections::HashSet;
 
 type Parsed = (Grid<i64>, Vec<Vec2>);
 
@@ -30,33 +29,19 @@ fn part2((grid, track): &Parsed) -> impl std::fmt::Display {
 }
 
 fn solve(grid: &Grid<i64>, track: &[Vec2], max_distance: i64) -> usize {
-    let mut cheats: HashSet<(Vec2, Vec2)> = Default::default();
+    let mut cheats = 0;
     for &from in track {
-        for distance in 1..=max_distance {
-            for x in 0..=distance {
-                let y = distance - x;
-                for v in [
-                    Vec2::new(x, y),
-                    Vec2::new(-x, y),
-                    Vec2::new(x, -y),
-                    Vec2::new(-x, -y),
-                ] {
-                    let to = from.translate(v);
-                    let start = grid[from];
-                    if let Some(&stop) = grid.get(&to) {
-                        if stop != i64::MAX
-                            && stop > start
-                            && (stop - start).abs() - distance >= 100
-                        {
-                            cheats.insert((from, to));
-                        }
-                    }
+        let start = grid[from];
+        for to in from.within_manhattan(max_distance) {
+            if let Some(&stop) = grid.get(&to) {
+                if stop != i64::MAX && stop > start && stop - start - from.manhattan(&to) >= 100 {
+                    cheats += 1;
                 }
             }
         }
     }
 
-    cheats.len()
+    cheats
 }
 
 fn map_track(grid: &Grid<u8>, start: &Vec2, &end: &Vec2) -> Vec<(Vec2, i64)> {
@@ -67,7 +52,7 @@ fn map_track(grid: &Grid<u8>, start: &Vec2, &end: &Vec2) -> Vec<(Vec2, i64)> {
     let mut track: Vec<(Vec2, i64)> = Default::default();
     let mut distance = 1;
 
-    let mut visited = HashSet::new();
+    let mut visited = BitGrid::like(grid);
     let mut cursor = grid.cursor(*start);
     'outer: while cursor.value() != &b'E' {
         visited.insert(cursor.pos());
@@ -1,7 +1,8 @@
+use aoc::memo::Memo;
 use aoc::*;
 use std::collections::HashMap;
 
-type KeyMap = HashMap<(u8, u8), (String, String)>;
+type KeyMap = HashMap<(u8, u8), [String; 2]>;
 type Parsed = (Vec<String>, KeyMap, Grid<u8>, KeyMap, Grid<u8>);
 
 fn parse_input(input: &str) -> Parsed {
@@ -17,7 +18,7 @@ fn parse_input(input: &str) -> Parsed {
 }
 
 fn build_keymap(keys: &Grid<u8>) -> KeyMap {
-    fn build_map(v: Vec2, hneg: u8, hpos: u8, vneg: u8, vpos: u8) -> (String, String) {
+    fn build_map(v: Vec2, hneg: u8, hpos: u8, vneg: u8, vpos: u8) -> [String; 2] {
         let mut horz = String::new();
         let mut vert = String::new();
         let hsym = if v.x.is_negative() { hneg } else { hpos };
@@ -28,7 +29,7 @@ fn build_keymap(keys: &Grid<u8>) -> KeyMap {
         for _ in 0..v.y.abs() {
             vert.push(vsym as char);
         }
-        (vert, horz)
+        [format!("{vert}{horz}A"), format!("{horz}{vert}A")]
     }
 
     let mut keymap = HashMap::new();
@@ -60,10 +61,10 @@ fn part2(input: &Parsed) -> impl std::fmt::Display {
 
 fn solve(depth: usize, (codes, nmap, nkey, dmap, dkey): &Parsed) -> usize {
     let mut result = 0;
-    let mut cache = HashMap::new();
+    let mut memo = Memo::new();
     for code in codes {
         let value: usize = parse(code.trim_end_matches('A'));
-        result += value * map(&mut cache, nmap, nkey, dmap, dkey, code, depth, 0);
+        result += value * map(&mut memo, nmap, nkey, dmap, dkey, code, depth, 0);
     }
     result
 }
@@ -82,63 +83,60 @@ fn validate_mapping(from: u8, sequence: &str, keys: &Grid<u8>) -> bool {
     true
 }
 
-fn map(
-    cache: &mut HashMap<(String, usize, usize), usize>,
-    num_mappings: &KeyMap,
+#[allow(clippy::too_many_arguments)]
+fn map<'a>(
+    memo: &mut Memo<(&'a str, usize), usize>,
+    num_mappings: &'a KeyMap,
     num_keypad: &Grid<u8>,
-    dir_mappings: &KeyMap,
+    dir_mappings: &'a KeyMap,
     dir_keypad: &Grid<u8>,
-    sequence: &str,
+    sequence: &'a str,
     limit: usize,
     depth: usize,
 ) -> usize {
-    if let Some(len) = cache.get(&(sequence.to_string(), limit, depth)) {
-        return *len;
-    }
-
     if depth == limit {
         return sequence.len();
     }
 
-    let mut current = b'A';
-    let mut total = 0;
-    for key in sequence.bytes() {
-        let (vmap, hmap) = if depth == 0 {
-            num_mappings.get(&(current, key)).unwrap()
-        } else {
-            dir_mappings.get(&(current, key)).unwrap()
-        };
-
-        let shortest = [format!("{vmap}{hmap}A"), format!("{hmap}{vmap}A")]
-            .iter()
-            .filter(|seq| {
-                if depth == 0 {
-                    validate_mapping(current, seq, num_keypad)
-                } else {
-                    validate_mapping(current, seq, dir_keypad)
-                }
-            })
-            .map(|path| {
-                map(
-                    cache,
-                    num_mappings,
-                    num_keypad,
-                    dir_mappings,
-                    dir_keypad,
-                    path,
-                    limit,
-                    depth + 1,
-                )
-            })
-            .min()
-            .unwrap();
-
-        total += shortest;
-        current = key;
-    }
-
-    cache.insert((sequence.to_string(), limit, depth), total);
-    total
+    memo.get_or_compute(&(sequence, depth), |memo| {
+        let mut current = b'A';
+        let mut total = 0;
+        for key in sequence.bytes() {
+            let paths = if depth == 0 {
+                num_mappings.get(&(current, key)).unwrap()
+            } else {
+                dir_mappings.get(&(current, key)).unwrap()
+            };
+
+            let shortest = paths
+                .iter()
+                .filter(|seq| {
+                    if depth == 0 {
+                        validate_mapping(current, seq, num_keypad)
+                    } else {
+                        validate_mapping(current, seq, dir_keypad)
+                    }
+                })
+                .map(|path| {
+                    map(
+                        memo,
+                        num_mappings,
+                        num_keypad,
+                        dir_mappings,
+                        dir_keypad,
+                        path,
+                        limit,
+                        depth + 1,
+                    )
+                })
+                .min()
+                .unwrap();
+
+            total += shortest;
+            current = key;
+        }
+        total
+    })
 }
 
 aoc::setup! {
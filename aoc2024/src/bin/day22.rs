@@ -1,6 +1,5 @@
 use aoc::*;
 use rayon::iter::{IntoParallelIterator, ParallelIterator};
-use std::collections::HashMap;
 
 type Parsed = Vec<i64>;
 
@@ -24,7 +23,7 @@ fn part1(secrets: &Parsed) -> impl std::fmt::Display {
 }
 
 fn part2(secrets: &Parsed) -> impl std::fmt::Display {
-    let mut map: HashMap<[i16; 4], Vec<i16>> = Default::default();
+    let mut map: FastMap<[i16; 4], Vec<i16>> = Default::default();
 
     let vendors = secrets.len();
     let secrets = secrets.clone();
@@ -1,66 +1,23 @@
-use std::collections::{HashMap, HashSet};
+use aoc::graph::Graph;
 
-type Parsed<'a> = Vec<(&'a str, &'a str)>;
+type Parsed<'a> = Graph<&'a str>;
 
-fn parse_input(input: &str) -> Parsed {
-    input.lines().filter_map(|s| s.split_once('-')).collect()
+fn parse_input(input: &str) -> Parsed<'_> {
+    Graph::undirected(input.lines().filter_map(|s| s.split_once('-')))
 }
 
-fn part1(pairs: &Parsed) -> impl std::fmt::Display {
-    let mut map: HashMap<&str, Vec<&str>> = HashMap::new();
-    for (a, b) in pairs {
-        map.entry(a).or_default().push(b);
-        map.entry(b).or_default().push(a);
-    }
-
-    let mut systems: HashSet<[&str; 3]> = HashSet::new();
-    for &key in map.keys().filter(|key| key.starts_with('t')) {
-        let connected = &map[key];
-        if connected.len() < 2 {
-            continue;
-        }
-
-        for i in 0..connected.len() {
-            for j in i + 1..connected.len() {
-                if i < j {
-                    let a = connected[i];
-                    let b = connected[j];
-                    if map[a].contains(&b) {
-                        let mut set = [key, a, b];
-                        set.sort();
-                        systems.insert(set);
-                    }
-                }
-            }
-        }
-    }
-
-    systems.len()
+fn part1(graph: &Parsed) -> impl std::fmt::Display {
+    graph
+        .triangles()
+        .filter(|triangle| triangle.iter().any(|&n| graph.label(n).starts_with('t')))
+        .count()
 }
 
-fn part2(pairs: &Parsed) -> impl std::fmt::Display {
-    let mut map: HashMap<&str, Vec<&str>> = HashMap::new();
-    for (a, b) in pairs {
-        map.entry(a).or_default().push(b);
-        map.entry(b).or_default().push(a);
-    }
-
-    let mut networks: Vec<HashSet<&str>> =
-        map.keys().map(|&key| HashSet::from_iter([key])).collect();
-
-    for network in networks.iter_mut() {
-        for comp in map.keys() {
-            if network.iter().all(|node| map[comp].contains(node)) {
-                network.insert(comp);
-            }
-        }
-    }
-
-    let mut clique: Vec<_> = networks
-        .into_iter()
-        .max_by_key(|network| network.len())
-        .unwrap()
+fn part2(graph: &Parsed) -> impl std::fmt::Display {
+    let mut clique: Vec<_> = graph
+        .max_clique()
         .into_iter()
+        .map(|n| *graph.label(n))
         .collect();
 
     clique.sort();
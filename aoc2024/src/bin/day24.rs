@@ -14,13 +14,13 @@ enum Op {
 }
 
 impl FromStr for Op {
-    type Err = ();
+    type Err = String;
     fn from_str(s: &str) -> Result<Self, Self::Err> {
         match s {
             "AND" => Ok(Self::And),
             "XOR" => Ok(Self::Xor),
             "OR" => Ok(Self::Or),
-            _ => Err(()),
+            _ => Err(format!("unknown operator {s:?}")),
         }
     }
 }
@@ -37,12 +37,12 @@ impl Op {
 
 #[derive(Debug)]
 enum Node {
-    Boolean { op: Op, left: usize, right: usize },
+    Boolean { op: Op, left: u32, right: u32 },
     Leaf { value: bool },
 }
 
 impl Node {
-    fn evaluate(&self, arena: &[Node]) -> bool {
+    fn evaluate(&self, arena: &Table<Node>) -> bool {
         match self {
             Self::Leaf { value } => *value,
             Self::Boolean { op, left, right } => {
@@ -54,37 +54,33 @@ impl Node {
     }
 }
 
-fn parse_input(input: &str) -> Parsed {
-    let (a, b) = input.split_once("\n\n").unwrap();
+fn parse_input(input: &str) -> Result<Parsed<'_>, ParseError> {
+    let [values, rules] = sections(input).exactly()?;
 
-    let initial_values = a
-        .lines()
-        .map(|line| {
-            let (wire, value) = line.split_once(": ").unwrap();
-            (wire, value == "1")
-        })
-        .collect();
+    let initial_values = values.try_map_lines(|line| {
+        let [wire, value] = scan!(line, "{}: {}")?;
+        Ok((wire, value == "1"))
+    })?;
 
-    let rules = b
-        .lines()
-        .map(|line| {
-            let parts: Vec<_> = line.split_whitespace().collect();
-            (parse(parts[1]), parts[0], parts[2], parts[4])
-        })
-        .collect();
+    let rules = rules.try_map_lines(|line| {
+        let [left, op, right, out] = scan!(line, "{} {} {} -> {}")?;
+        let op = try_parse(op).map_err(|err| err.offset_column(left.len() + 1))?;
+        Ok((op, left, right, out))
+    })?;
 
-    (initial_values, rules)
+    Ok((initial_values, rules))
 }
 
 fn part1((values, rules): &Parsed) -> impl std::fmt::Display {
-    let (expr, node_indices) = build_expression(values, rules);
-    value_from_prefix('z', &node_indices, &expr)
+    let (expr, wires) = build_expression(values, rules);
+    value_from_prefix('z', &wires, &expr)
 }
 
 fn part2((values, rules): &Parsed) -> impl std::fmt::Display {
-    let (_, node_indices) = build_expression(values, rules);
-    let &last_z = node_indices
-        .keys()
+    let (_, wires) = build_expression(values, rules);
+    let &last_z = wires
+        .labels()
+        .iter()
         .filter(|wire| wire.starts_with('z'))
         .max_by_key(|wire| {
             let num: u8 = wire.trim_start_matches('z').parse().unwrap();
@@ -138,63 +134,36 @@ fn part2((values, rules): &Parsed) -> impl std::fmt::Display {
 fn build_expression<'a>(
     values: &HashMap<&'a str, bool>,
     rules: &[(Op, &'a str, &'a str, &'a str)],
-) -> (Vec<Node>, HashMap<&'a str, usize>) {
-    let mut expr: Vec<Node> = Vec::with_capacity(rules.len());
-    let mut node_indices = HashMap::new();
+) -> (Table<Node>, Interner<&'a str>) {
+    let mut wires = Interner::new();
+    for &(_, left, right, result) in rules {
+        wires.intern(left);
+        wires.intern(right);
+        wires.intern(result);
+    }
+
+    let mut expr = wires.table_with(|(_, wire)| Node::Leaf {
+        value: values.get(wire).copied().unwrap_or(false),
+    });
     for &(op, left, right, result) in rules {
-        let left_index = if let Some(&left_index) = node_indices.get(left) {
-            left_index
-        } else {
-            let left_index = expr.len();
-            let &value = values.get(left).unwrap_or(&false);
-            node_indices.insert(left, left_index);
-            expr.push(Node::Leaf { value });
-            left_index
-        };
-
-        let right_index = if let Some(&right_index) = node_indices.get(right) {
-            right_index
-        } else {
-            let right_index = expr.len();
-            let &value = values.get(right).unwrap_or(&false);
-            node_indices.insert(right, right_index);
-            expr.push(Node::Leaf { value });
-            right_index
-        };
-
-        if let Some(&result_node) = node_indices.get(result) {
-            expr[result_node] = Node::Boolean {
-                op,
-                left: left_index,
-                right: right_index,
-            };
-        } else {
-            let result_node = expr.len();
-            node_indices.insert(result, result_node);
-            expr.push(Node::Boolean {
-                op,
-                left: left_index,
-                right: right_index,
-            })
-        }
+        let [left, right, result] = [left, right, result].map(|wire| wires.id(wire).unwrap());
+        expr[result] = Node::Boolean { op, left, right };
     }
 
-    (expr, node_indices)
+    (expr, wires)
 }
 
-fn value_from_prefix(pre: char, node_indices: &HashMap<&str, usize>, expr: &[Node]) -> u64 {
-    let mut zs: Vec<_> = node_indices
-        .keys()
-        .filter(|wire| wire.starts_with(pre))
-        .copied()
+fn value_from_prefix(pre: char, wires: &Interner<&str>, expr: &Table<Node>) -> u64 {
+    let mut zs: Vec<_> = wires
+        .iter()
+        .filter(|(_, wire)| wire.starts_with(pre))
         .collect();
 
-    zs.sort();
+    zs.sort_by_key(|&(_, wire)| wire);
 
     let mut result: u64 = 0;
-    for wire in zs.into_iter().rev() {
-        let index = node_indices[wire];
-        let value = expr[index].evaluate(expr) as u64;
+    for (id, _) in zs.into_iter().rev() {
+        let value = expr[id].evaluate(expr) as u64;
 
         result <<= 1;
         result |= value;
@@ -204,7 +173,7 @@ fn value_from_prefix(pre: char, node_indices: &HashMap<&str, usize>, expr: &[Nod
 }
 
 aoc::setup! {
-    day24, parse_input;
+    day24, parse_input?;
     part1 == 2024,
     part2 == "ffh,hwm,kjc,mjb,ntg,rvg,tgd,wpb,z02,z03,z05,z06,z07,z08,z10,z11"
 }
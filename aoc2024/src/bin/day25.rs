@@ -1,4 +1,4 @@
-use aoc::gridify_ascii;
+use aoc::{gridify_ascii, sections};
 
 type Set = [u8; 5];
 type Parsed = (Vec<Set>, Vec<Set>);
@@ -7,7 +7,7 @@ fn parse_input(input: &str) -> Parsed {
     let mut locks = vec![];
     let mut keys = vec![];
 
-    for block in input.split("\n\n") {
+    for block in sections(input) {
         let block = gridify_ascii(block.lines());
         let list = if block.iter_row(0).all(|&v| v == b'#') {
             &mut locks
@@ -15,9 +15,12 @@ fn parse_input(input: &str) -> Parsed {
             &mut keys
         };
 
+        // Each row of the transposed block is one column of pins, which
+        // includes a cell of the solid top or bottom row.
+        let columns = block.transpose();
         let mut comb = [0, 0, 0, 0, 0];
-        for x in 0..5 {
-            comb[x] = (1..6).filter(|&y| block[(x, y)] == b'#').count() as u8;
+        for (x, count) in comb.iter_mut().enumerate() {
+            *count = columns.iter_row(x).filter(|&&v| v == b'#').count() as u8 - 1;
         }
         list.push(comb);
     }